@@ -0,0 +1,194 @@
+// programs/sb_randomness/src/bankroll.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::{Table, TreasuryAccount};
+use crate::errors::ErrorCode;
+
+// Share mint decimals, one share per lamport or base unit when the bankroll opens
+pub const SHARE_DECIMALS: u8 = 9;
+
+// Slots a liquidity withdrawal stays runnable once it unlocks, about a minute
+pub const WITHDRAWAL_WINDOW_SLOTS: u64 = 150;
+
+/// A liquidity provider's pending redemption, payable from `unlock_slot` through `expiry_slot`.
+///
+/// Shares stay in the provider's wallet until the withdrawal runs, so they keep
+/// carrying the house's wins and losses through the cooldown. The window closes so a
+/// request can't be held in reserve for the moment a result goes against the house.
+#[account]
+#[derive(Default)]
+pub struct LiquidityWithdrawal {
+    pub provider: Pubkey,
+    pub table: Pubkey,
+    pub shares: u64,      // Shares to burn when the withdrawal runs
+    pub unlock_slot: u64, // First slot the withdrawal can run in
+    pub expiry_slot: u64, // Last slot the withdrawal can run in, after it a new request is needed
+    pub bump: u8,
+}
+
+impl LiquidityWithdrawal {
+    /// Checks `slot` falls inside the withdrawal's window.
+    pub fn check_window(&self, slot: u64) -> Result<()> {
+        require!(slot >= self.unlock_slot, ErrorCode::WithdrawalCoolingDown);
+        require!(slot <= self.expiry_slot, ErrorCode::WithdrawalExpired);
+        Ok(())
+    }
+}
+
+/// Shares can't be priced while a committed roll waits to settle: its result may already
+/// be public while the bankroll still counts the roll's reserve as the house's.
+pub fn ensure_no_roll_in_flight(table: &Table) -> Result<()> {
+    require!(table.committed_rolls == 0, ErrorCode::RollInFlight);
+    Ok(())
+}
+
+/// What the bankroll is worth to its shareholders: the house's funds less the payouts
+/// already won on settled rolls and not yet claimed. Reserves for open rolls stay in,
+/// the house may still win those.
+pub fn nav(table: &Table, treasury_available: u64) -> u64 {
+    table
+        .house_available(treasury_available)
+        .saturating_sub(table.settled_liabilities)
+}
+
+/// Shares minted for a deposit of `amount` into a bankroll worth `nav`, rounded down.
+/// An empty bankroll issues one share per unit deposited.
+pub fn shares_for_deposit(amount: u64, supply: u64, nav: u64) -> Result<u64> {
+    if supply == 0 {
+        return Ok(amount);
+    }
+    // Shares still out with nothing behind them would be diluted to nothing
+    require!(nav > 0, ErrorCode::BankrollInsolvent);
+    let shares = (amount as u128)
+        .checked_mul(supply as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / nav as u128;
+    u64::try_from(shares).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// What `shares` redeem for out of a bankroll worth `nav`, rounded down.
+/// The rounding dust stays with the remaining holders.
+pub fn share_value(shares: u64, supply: u64, nav: u64) -> Result<u64> {
+    require!(shares <= supply, ErrorCode::InsufficientShares);
+    let value = (nav as u128)
+        .checked_mul(shares as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / supply as u128;
+    u64::try_from(value).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Mints `amount` shares to `to`, signed by the treasury PDA that owns the share mint.
+pub fn mint_shares<'info>(
+    table: &Account<'info, Table>,
+    treasury_pda_account: &Account<'info, TreasuryAccount>,
+    share_mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    share_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let table_key = table.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", table_key.as_ref(), &[table.treasury_bump]]];
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            share_token_program.to_account_info(),
+            MintTo {
+                mint: share_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: treasury_pda_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn house_results_flow_into_share_value() {
+        // The operator's 1_000_000 seeds the bankroll one share per lamport
+        let mut supply = shares_for_deposit(1_000_000, 0, 0).unwrap();
+        let mut nav = 1_000_000;
+        assert_eq!(supply, 1_000_000);
+
+        // An outside provider matches it at the same price
+        let lp = shares_for_deposit(1_000_000, supply, nav).unwrap();
+        assert_eq!(lp, 1_000_000);
+        supply += lp;
+        nav += 1_000_000;
+
+        // The house wins 500_000, a later deposit buys in at the higher price
+        nav += 500_000;
+        let late = shares_for_deposit(500_000, supply, nav).unwrap();
+        assert_eq!(late, 400_000);
+        supply += late;
+        nav += 500_000;
+
+        // A losing night is shared pro rata as well
+        nav -= 600_000;
+        assert_eq!(share_value(lp, supply, nav).unwrap(), 1_000_000);
+        assert_eq!(share_value(late, supply, nav).unwrap(), 400_000);
+    }
+
+    #[test]
+    fn rounding_favours_the_remaining_holders() {
+        assert_eq!(shares_for_deposit(10, 3, 7).unwrap(), 4);
+        assert_eq!(share_value(4, 7, 17).unwrap(), 9);
+        assert_eq!(share_value(7, 7, 17).unwrap(), 17);
+    }
+
+    #[test]
+    fn insolvent_bankroll_takes_no_deposits() {
+        let err = shares_for_deposit(1_000, 500, 0).unwrap_err();
+        assert_eq!(err, ErrorCode::BankrollInsolvent.into());
+    }
+
+    #[test]
+    fn cannot_redeem_more_than_the_supply() {
+        let err = share_value(11, 10, 1_000).unwrap_err();
+        assert_eq!(err, ErrorCode::InsufficientShares.into());
+    }
+
+    #[test]
+    fn unclaimed_winnings_are_not_priced_into_shares() {
+        let table = Table {
+            outstanding_liabilities: 900_000,
+            settled_liabilities: 600_000,
+            ..Default::default()
+        };
+        assert_eq!(nav(&table, 2_000_000), 1_400_000);
+        // A late joiner buys in at the price net of what winners are owed
+        assert_eq!(shares_for_deposit(700_000, 1_000_000, nav(&table, 2_000_000)).unwrap(), 500_000);
+    }
+
+    #[test]
+    fn withdrawal_runs_only_inside_its_window() {
+        let withdrawal = LiquidityWithdrawal {
+            unlock_slot: 1_000,
+            expiry_slot: 1_000 + WITHDRAWAL_WINDOW_SLOTS,
+            ..Default::default()
+        };
+        let err = withdrawal.check_window(999).unwrap_err();
+        assert_eq!(err, ErrorCode::WithdrawalCoolingDown.into());
+        withdrawal.check_window(1_000).unwrap();
+        withdrawal.check_window(1_000 + WITHDRAWAL_WINDOW_SLOTS).unwrap();
+        let err = withdrawal.check_window(1_001 + WITHDRAWAL_WINDOW_SLOTS).unwrap_err();
+        assert_eq!(err, ErrorCode::WithdrawalExpired.into());
+    }
+
+    #[test]
+    fn shares_are_not_priced_with_a_roll_in_flight() {
+        let mut table = Table {
+            committed_rolls: 1,
+            ..Default::default()
+        };
+        let err = ensure_no_roll_in_flight(&table).unwrap_err();
+        assert_eq!(err, ErrorCode::RollInFlight.into());
+        table.committed_rolls = 0;
+        ensure_no_roll_in_flight(&table).unwrap();
+    }
+}
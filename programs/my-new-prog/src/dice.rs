@@ -0,0 +1,609 @@
+// programs/sb_randomness/src/dice.rs
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ErrorCode;
+
+/// Fewest and most faces a table's die can have.
+pub const MIN_DIE_SIDES: u8 = 4;
+pub const MAX_DIE_SIDES: u8 = 100;
+
+/// Most dice a single roll can throw. Multi-dice tables always use six-sided dice.
+pub const MAX_DICE: usize = 3;
+const MULTI_DICE_SIDES: u8 = 6;
+
+// Bit pattern of the odd faces, bit 0 is face 1
+const ODD_PATTERN: u128 = 0x5555_5555_5555_5555_5555_5555_5555_5555;
+
+pub fn validate_dice(sides: u8, dice_count: u8) -> Result<()> {
+    require!(
+        (MIN_DIE_SIDES..=MAX_DIE_SIDES).contains(&sides),
+        ErrorCode::InvalidDieSides
+    );
+    require!(
+        (1..=MAX_DICE as u8).contains(&dice_count),
+        ErrorCode::InvalidDiceCount
+    );
+    // The published payout table is only defined for six-sided dice
+    require!(
+        dice_count == 1 || sides == MULTI_DICE_SIDES,
+        ErrorCode::InvalidDiceCount
+    );
+    Ok(())
+}
+
+/// Maps the revealed 32-byte Switchboard value to a face of a die with `sides` faces.
+///
+/// Uses rejection sampling over the bytes of the value: bytes at or above the
+/// largest multiple of `sides` that fits in a byte are skipped, so every face is
+/// backed by the same number of byte values. In the case that every byte is
+/// rejected (under 2^-69 even for a d100), falls back to reducing the low 128 bits
+/// modulo the number of sides, whose bias is far below anything observable.
+pub fn roll_die(randomness: &[u8; 32], sides: u8) -> u8 {
+    let rejection_limit = 256 - (256 % sides as u16);
+    for byte in randomness.iter() {
+        if (*byte as u16) < rejection_limit {
+            return byte % sides + 1;
+        }
+    }
+
+    let mut wide = [0u8; 16];
+    wide.copy_from_slice(&randomness[..16]);
+    (u128::from_le_bytes(wide) % sides as u128) as u8 + 1
+}
+
+/// Throws `dice_count` dice from one revealed value. The first die reads the value
+/// itself, every further die reads a hash of the value and its index so the dice
+/// are independent. Unused slots stay zero.
+pub fn roll_dice(randomness: &[u8; 32], sides: u8, dice_count: u8) -> [u8; MAX_DICE] {
+    let mut dice = [0u8; MAX_DICE];
+    dice[0] = roll_die(randomness, sides);
+    for (index, die) in dice.iter_mut().enumerate().take(dice_count as usize).skip(1) {
+        let derived = hashv(&[randomness, &[index as u8]]).to_bytes();
+        *die = roll_die(&derived, sides);
+    }
+    dice
+}
+
+// Every distinct throw of `dice_count` six-sided dice, each sorted ascending, in a
+// fixed order. A throw's position is its slot in `RollState::face_liabilities`.
+fn multi_dice_outcomes(dice_count: u8) -> Vec<[u8; MAX_DICE]> {
+    let mut outcomes = Vec::new();
+    for a in 1..=MULTI_DICE_SIDES {
+        for b in a..=MULTI_DICE_SIDES {
+            if dice_count == 2 {
+                outcomes.push([a, b, 0]);
+                continue;
+            }
+            for c in b..=MULTI_DICE_SIDES {
+                outcomes.push([a, b, c]);
+            }
+        }
+    }
+    outcomes
+}
+
+// Slot of a throw in the outcome order, faces for a single die, sorted throws otherwise
+pub fn outcome_index(dice: &[u8; MAX_DICE], dice_count: u8) -> Option<usize> {
+    if dice_count == 1 {
+        return dice[0].checked_sub(1).map(usize::from);
+    }
+    let mut sorted = *dice;
+    sorted[..dice_count as usize].sort_unstable();
+    multi_dice_outcomes(dice_count).iter().position(|outcome| *outcome == sorted)
+}
+
+// Faces `low` to `high` inclusive as a bitmask, empty if `low > high`
+fn face_range(low: u8, high: u8) -> u128 {
+    if low > high {
+        return 0;
+    }
+    let below_high = (1u128 << high) - 1;
+    let below_low = (1u128 << (low - 1)) - 1;
+    below_high & !below_low
+}
+
+/// What a bet wins on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BetKind {
+    Exact(u8),   // A single face
+    Faces(u128), // Any face whose bit is set, bit 0 is face 1
+    High,        // The top half of the faces, the middle face of an odd die loses
+    Low,         // The bottom half of the faces
+    Odd,
+    Even,
+    Under(u8),   // Any face strictly below the target, e.g. roll under 50 on a d100
+    Over(u8),    // Any face strictly above the target
+
+    // Multi-dice tables only, paid from `multi_dice_multiple`
+    Sum(u8),     // The dice add up to exactly this
+    Double(u8),  // At least two dice show this face
+    Triple(u8),  // All three dice show this face
+    AnyTriple,   // All three dice show the same face
+    Big,         // Three dice totalling 11-17, loses on any triple
+    Small,       // Three dice totalling 4-10, loses on any triple
+}
+
+/// Published gross payout of a multi-dice bet as a multiple of the stake, or `None`
+/// if the bet isn't offered for this many dice. Every entry pays below true odds,
+/// which is the house edge; the table's commission still applies on claim.
+pub fn multi_dice_multiple(kind: &BetKind, dice_count: u8) -> Option<u64> {
+    let multiple = match (dice_count, *kind) {
+        // Craps-style two dice, true odds in brackets
+        (2, BetKind::Sum(2 | 12)) => 31,      // 30 to 1 (35 to 1)
+        (2, BetKind::Sum(3 | 11)) => 16,      // 15 to 1 (17 to 1)
+        (2, BetKind::Sum(4 | 10)) => 11,      // 10 to 1 (11 to 1)
+        (2, BetKind::Sum(5 | 9)) => 8,        // 7 to 1 (8 to 1)
+        (2, BetKind::Sum(6 | 8)) => 6,        // 5 to 1 (6.2 to 1)
+        (2, BetKind::Sum(7)) => 5,            // 4 to 1 (5 to 1)
+        (2, BetKind::Double(1..=6)) => 31,    // 30 to 1 (35 to 1)
+
+        // Sic Bo three dice
+        (3, BetKind::Sum(4 | 17)) => 51,      // 50 to 1 (71 to 1)
+        (3, BetKind::Sum(5 | 16)) => 19,      // 18 to 1 (35 to 1)
+        (3, BetKind::Sum(6 | 15)) => 15,      // 14 to 1 (20.6 to 1)
+        (3, BetKind::Sum(7 | 14)) => 13,      // 12 to 1 (13.4 to 1)
+        (3, BetKind::Sum(8 | 13)) => 9,       // 8 to 1 (9.3 to 1)
+        (3, BetKind::Sum(9..=12)) => 7,       // 6 to 1 (7.6 to 1 at best)
+        (3, BetKind::Double(1..=6)) => 11,    // 10 to 1 (12.5 to 1)
+        (3, BetKind::Triple(1..=6)) => 151,   // 150 to 1 (215 to 1)
+        (3, BetKind::AnyTriple) => 25,        // 24 to 1 (35 to 1)
+        (3, BetKind::Big | BetKind::Small) => 2, // 1 to 1 (1.06 to 1)
+        _ => return None,
+    };
+    Some(multiple)
+}
+
+impl BetKind {
+    fn is_multi_dice(&self) -> bool {
+        matches!(
+            self,
+            BetKind::Sum(_)
+                | BetKind::Double(_)
+                | BetKind::Triple(_)
+                | BetKind::AnyTriple
+                | BetKind::Big
+                | BetKind::Small
+        )
+    }
+
+    // Whether a multi-dice bet wins on `dice`
+    fn hits(&self, dice: &[u8]) -> bool {
+        let sum: u8 = dice.iter().sum();
+        let is_triple = dice.len() == 3 && dice.iter().all(|d| *d == dice[0]);
+        match *self {
+            BetKind::Sum(target) => sum == target,
+            BetKind::Double(face) => dice.iter().filter(|d| **d == face).count() >= 2,
+            BetKind::Triple(face) => is_triple && dice[0] == face,
+            BetKind::AnyTriple => is_triple,
+            BetKind::Big => (11..=17).contains(&sum) && !is_triple,
+            BetKind::Small => (4..=10).contains(&sum) && !is_triple,
+            _ => false,
+        }
+    }
+
+    /// The winning outcomes as a bitmask over the slots of `RollState::face_liabilities`:
+    /// faces for a single die, sorted throws for multi-dice tables.
+    pub fn outcome_mask(&self, sides: u8, dice_count: u8) -> Result<u128> {
+        if dice_count == 1 {
+            require!(!self.is_multi_dice(), ErrorCode::InvalidGuess);
+            return self.face_mask(sides);
+        }
+
+        require!(
+            multi_dice_multiple(self, dice_count).is_some(),
+            ErrorCode::InvalidGuess
+        );
+        let mask = multi_dice_outcomes(dice_count)
+            .iter()
+            .enumerate()
+            .filter(|(_, outcome)| self.hits(&outcome[..dice_count as usize]))
+            .fold(0u128, |mask, (index, _)| mask | (1 << index));
+        Ok(mask)
+    }
+
+    /// The winning faces on a single die with `sides` faces as a bitmask.
+    /// A bet has to be able to both win and lose.
+    fn face_mask(&self, sides: u8) -> Result<u128> {
+        let all_faces = face_range(1, sides);
+        let mask = match *self {
+            BetKind::Exact(face) => {
+                require!((1..=sides).contains(&face), ErrorCode::InvalidGuess);
+                1 << (face - 1)
+            }
+            BetKind::Faces(mask) => mask,
+            BetKind::High => face_range(sides - sides / 2 + 1, sides),
+            BetKind::Low => face_range(1, sides / 2),
+            BetKind::Odd => ODD_PATTERN & all_faces,
+            BetKind::Even => !ODD_PATTERN & all_faces,
+            BetKind::Under(target) => face_range(1, target.saturating_sub(1).min(sides)),
+            BetKind::Over(target) => face_range(target.saturating_add(1), sides),
+            _ => return err!(ErrorCode::InvalidGuess),
+        };
+        require!(
+            mask != 0 && mask != all_faces && mask & !all_faces == 0,
+            ErrorCode::InvalidGuess
+        );
+        Ok(mask)
+    }
+
+    pub fn wins(&self, dice: &[u8; MAX_DICE], sides: u8, dice_count: u8) -> Result<bool> {
+        if dice_count == 1 && !(1..=sides).contains(&dice[0]) {
+            return Ok(false);
+        }
+        let Some(index) = outcome_index(dice, dice_count) else {
+            return Ok(false);
+        };
+        Ok(self.outcome_mask(sides, dice_count)? & (1 << index) != 0)
+    }
+
+    /// Gross payout of a winning bet. A single die pays fair odds,
+    /// `stake * sides / winning faces` rounded down, and the house edge is the
+    /// commission taken from it on claim. Multi-dice bets pay the published table.
+    pub fn gross_payout(&self, stake: u64, sides: u8, dice_count: u8) -> Result<u64> {
+        if dice_count > 1 {
+            let multiple = multi_dice_multiple(self, dice_count).ok_or(ErrorCode::InvalidGuess)?;
+            return Ok(stake.checked_mul(multiple).ok_or(ErrorCode::MathOverflow)?);
+        }
+
+        let winning_faces = self.face_mask(sides)?.count_ones() as u64;
+        Ok(stake
+            .checked_mul(sides as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+            / winning_faces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hashv;
+
+    const D6: u8 = 6;
+
+    fn seed(i: u64) -> [u8; 32] {
+        hashv(&[b"dice-seed", &i.to_le_bytes()]).to_bytes()
+    }
+
+    #[test]
+    fn faces_are_always_in_range() {
+        for sides in [4, 6, 20, 100] {
+            for i in 0..10_000 {
+                let face = roll_die(&seed(i), sides);
+                assert!((1..=sides).contains(&face), "face {} out of range for d{}", face, sides);
+            }
+        }
+    }
+
+    fn assert_uniform(sides: u8, rolls: u64) {
+        let mut counts = vec![0u64; sides as usize];
+        for i in 0..rolls {
+            counts[(roll_die(&seed(i), sides) - 1) as usize] += 1;
+        }
+
+        // Each face expects 10_000 hits; a 3% band is over 7 standard deviations.
+        let expected = rolls / sides as u64;
+        for (face, count) in counts.iter().enumerate() {
+            let diff = count.abs_diff(expected);
+            assert!(
+                diff * 100 <= expected * 3,
+                "d{} face {} hit {} times, expected about {}",
+                sides,
+                face + 1,
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn faces_are_uniform_over_fixed_seeds() {
+        assert_uniform(D6, 60_000);
+        assert_uniform(20, 200_000);
+    }
+
+    #[test]
+    fn every_accepted_byte_value_maps_evenly() {
+        for sides in MIN_DIE_SIDES..=MAX_DIE_SIDES {
+            let rejection_limit = 256 - (256 % sides as u16);
+            let mut counts = vec![0u16; sides as usize];
+            for byte in 0..rejection_limit {
+                let mut value = [0u8; 32];
+                value[0] = byte as u8;
+                counts[(roll_die(&value, sides) - 1) as usize] += 1;
+            }
+            assert!(counts.iter().all(|c| *c == counts[0]), "d{} is biased", sides);
+        }
+    }
+
+    #[test]
+    fn rejected_bytes_are_skipped() {
+        let mut value = [0u8; 32];
+        value[0] = 255;
+        value[1] = 252;
+        value[2] = 9;
+        assert_eq!(roll_die(&value, D6), 9 % D6 + 1);
+
+        // A d100 rejects everything from 200 up
+        value[0] = 200;
+        value[1] = 250;
+        value[2] = 199;
+        assert_eq!(roll_die(&value, 100), 100);
+    }
+
+    #[test]
+    fn die_sizes_outside_d4_to_d100_are_rejected() {
+        for sides in [0, 1, 3, 101, 255] {
+            assert_eq!(validate_dice(sides, 1).unwrap_err(), ErrorCode::InvalidDieSides.into());
+        }
+        validate_dice(4, 1).unwrap();
+        validate_dice(100, 1).unwrap();
+    }
+
+    #[test]
+    fn multi_dice_tables_need_two_or_three_six_sided_dice() {
+        for (sides, dice_count) in [(6, 0), (6, 4), (20, 2), (4, 3)] {
+            assert_eq!(
+                validate_dice(sides, dice_count).unwrap_err(),
+                ErrorCode::InvalidDiceCount.into()
+            );
+        }
+        validate_dice(D6, 2).unwrap();
+        validate_dice(D6, 3).unwrap();
+    }
+
+    // Checks `kind` against every outcome and that the payout is fair for its odds
+    fn assert_bet(kind: BetKind, sides: u8, winners: &[u8], stake: u64, payout: u64) {
+        for face in 1..=sides {
+            assert_eq!(
+                kind.wins(&[face, 0, 0], sides, 1).unwrap(),
+                winners.contains(&face),
+                "{:?} on a d{} roll of {}",
+                kind,
+                sides,
+                face
+            );
+        }
+        assert_eq!(kind.gross_payout(stake, sides, 1).unwrap(), payout);
+    }
+
+    #[test]
+    fn exact_bets_win_on_their_face_only() {
+        for face in 1..=D6 {
+            assert_bet(BetKind::Exact(face), D6, &[face], 100, 600);
+        }
+        assert_bet(BetKind::Exact(100), 100, &[100], 100, 10_000);
+    }
+
+    #[test]
+    fn high_low_odd_even_bets_pay_double() {
+        assert_bet(BetKind::High, D6, &[4, 5, 6], 100, 200);
+        assert_bet(BetKind::Low, D6, &[1, 2, 3], 100, 200);
+        assert_bet(BetKind::Odd, D6, &[1, 3, 5], 100, 200);
+        assert_bet(BetKind::Even, D6, &[2, 4, 6], 100, 200);
+    }
+
+    #[test]
+    fn middle_face_of_an_odd_die_is_neither_high_nor_low() {
+        assert_bet(BetKind::High, 5, &[4, 5], 100, 250);
+        assert_bet(BetKind::Low, 5, &[1, 2], 100, 250);
+        assert_bet(BetKind::Odd, 5, &[1, 3, 5], 90, 150);
+    }
+
+    #[test]
+    fn face_set_bets_pay_by_number_of_faces() {
+        assert_bet(BetKind::Faces(0b000001), D6, &[1], 100, 600);
+        assert_bet(BetKind::Faces(0b100001), D6, &[1, 6], 100, 300);
+        assert_bet(BetKind::Faces(0b010110), D6, &[2, 3, 5], 100, 200);
+        assert_bet(BetKind::Faces(0b011011), D6, &[1, 2, 4, 5], 100, 150);
+        // 6 / 5 does not divide evenly, the remainder stays with the house
+        assert_bet(BetKind::Faces(0b111110), D6, &[2, 3, 4, 5, 6], 100, 120);
+    }
+
+    #[test]
+    fn every_face_set_pays_fair_odds() {
+        for mask in 1..(1u128 << D6) - 1 {
+            let kind = BetKind::Faces(mask);
+            let winners: Vec<u8> = (1..=D6).filter(|f| mask & (1 << (f - 1)) != 0).collect();
+            let payout = 60 * D6 as u64 / winners.len() as u64;
+            assert_bet(kind, D6, &winners, 60, payout);
+        }
+    }
+
+    #[test]
+    fn roll_under_and_over_scale_with_the_target() {
+        assert_bet(BetKind::Under(3), D6, &[1, 2], 100, 300);
+        assert_bet(BetKind::Over(4), D6, &[5, 6], 100, 300);
+
+        let under_11: Vec<u8> = (1..=10).collect();
+        assert_bet(BetKind::Under(11), 20, &under_11, 100, 200);
+        let under_50: Vec<u8> = (1..=49).collect();
+        assert_bet(BetKind::Under(50), 100, &under_50, 490, 1_000);
+        let over_95: Vec<u8> = (96..=100).collect();
+        assert_bet(BetKind::Over(95), 100, &over_95, 100, 2_000);
+    }
+
+    #[test]
+    fn bets_that_cannot_both_win_and_lose_are_rejected() {
+        for kind in [
+            BetKind::Exact(0),
+            BetKind::Exact(7),
+            BetKind::Faces(0),
+            BetKind::Faces(0b111111),
+            BetKind::Faces(0b1000000),
+            BetKind::Under(1),
+            BetKind::Under(7),
+            BetKind::Over(6),
+            BetKind::Over(0),
+            BetKind::Under(255),
+            BetKind::Over(255),
+            BetKind::Sum(7),
+            BetKind::Big,
+        ] {
+            assert_eq!(kind.outcome_mask(D6, 1).unwrap_err(), ErrorCode::InvalidGuess.into());
+        }
+    }
+
+    #[test]
+    fn all_rejected_bytes_fall_back_to_wide_modulo() {
+        let value = [255u8; 32];
+        for sides in [D6, 20, 100] {
+            let expected = (u128::MAX % sides as u128) as u8 + 1;
+            assert_eq!(roll_die(&value, sides), expected);
+        }
+    }
+
+    // Every ordered throw of `dice_count` six-sided dice
+    fn all_throws(dice_count: u8) -> Vec<[u8; MAX_DICE]> {
+        let mut throws = vec![[0u8; MAX_DICE]];
+        for die in 0..dice_count as usize {
+            throws = throws
+                .into_iter()
+                .flat_map(|throw| {
+                    (1..=D6).map(move |face| {
+                        let mut next = throw;
+                        next[die] = face;
+                        next
+                    })
+                })
+                .collect();
+        }
+        throws
+    }
+
+    // Every multi-dice bet offered for `dice_count` dice
+    fn offered_bets(dice_count: u8) -> Vec<BetKind> {
+        let mut bets: Vec<BetKind> = (0..=19).map(BetKind::Sum).collect();
+        bets.extend((0..=7).map(BetKind::Double));
+        bets.extend((0..=7).map(BetKind::Triple));
+        bets.extend([BetKind::AnyTriple, BetKind::Big, BetKind::Small]);
+        bets.retain(|kind| multi_dice_multiple(kind, dice_count).is_some());
+        bets
+    }
+
+    // The rules of each bet written out directly against a throw
+    fn expected_win(kind: BetKind, throw: &[u8]) -> bool {
+        let sum: u8 = throw.iter().sum();
+        let count = |face: u8| throw.iter().filter(|d| **d == face).count();
+        let triple = throw.len() == 3 && count(throw[0]) == 3;
+        match kind {
+            BetKind::Sum(target) => sum == target,
+            BetKind::Double(face) => count(face) >= 2,
+            BetKind::Triple(face) => count(face) == 3,
+            BetKind::AnyTriple => triple,
+            BetKind::Big => sum >= 11 && !triple,
+            BetKind::Small => sum <= 10 && !triple,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn multi_dice_outcomes_cover_every_throw() {
+        assert_eq!(multi_dice_outcomes(2).len(), 21);
+        assert_eq!(multi_dice_outcomes(3).len(), 56);
+        for dice_count in [2, 3] {
+            for throw in all_throws(dice_count) {
+                assert!(outcome_index(&throw, dice_count).is_some(), "{:?}", throw);
+            }
+        }
+    }
+
+    #[test]
+    fn offered_bets_match_the_published_table() {
+        // Sums 2-12 and six doubles for craps-style tables
+        assert_eq!(offered_bets(2).len(), 11 + 6);
+        // Sums 4-17, six doubles, six triples, any triple, big and small for Sic Bo
+        assert_eq!(offered_bets(3).len(), 14 + 6 + 6 + 3);
+    }
+
+    #[test]
+    fn multi_dice_bets_follow_their_rules_on_every_throw() {
+        for dice_count in [2, 3] {
+            for kind in offered_bets(dice_count) {
+                for throw in all_throws(dice_count) {
+                    assert_eq!(
+                        kind.wins(&throw, D6, dice_count).unwrap(),
+                        expected_win(kind, &throw[..dice_count as usize]),
+                        "{:?} on {:?}",
+                        kind,
+                        throw
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn published_payouts_keep_a_house_edge() {
+        for dice_count in [2, 3] {
+            let throws = all_throws(dice_count);
+            for kind in offered_bets(dice_count) {
+                let winning = throws
+                    .iter()
+                    .filter(|throw| kind.wins(throw, D6, dice_count).unwrap())
+                    .count() as u64;
+                let payout = kind.gross_payout(100, D6, dice_count).unwrap();
+                assert!(winning > 0, "{:?} can never win", kind);
+                assert!(
+                    winning * payout < throws.len() as u64 * 100,
+                    "{:?} pays {} on {} of {} throws",
+                    kind,
+                    payout,
+                    winning,
+                    throws.len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sic_bo_triples_beat_big_and_small() {
+        assert!(!BetKind::Big.wins(&[4, 4, 4], D6, 3).unwrap());
+        assert!(!BetKind::Small.wins(&[2, 2, 2], D6, 3).unwrap());
+        assert!(BetKind::Double(2).wins(&[2, 2, 2], D6, 3).unwrap());
+        assert!(BetKind::Triple(2).wins(&[2, 2, 2], D6, 3).unwrap());
+        assert!(BetKind::Big.wins(&[6, 5, 1], D6, 3).unwrap());
+        assert_eq!(BetKind::Triple(2).gross_payout(10, D6, 3).unwrap(), 1_510);
+    }
+
+    #[test]
+    fn bets_only_run_on_the_tables_they_are_made_for() {
+        for kind in [BetKind::Exact(3), BetKind::High, BetKind::Faces(0b11)] {
+            assert_eq!(kind.outcome_mask(D6, 2).unwrap_err(), ErrorCode::InvalidGuess.into());
+        }
+        for kind in [BetKind::Sum(1), BetKind::Sum(13), BetKind::Triple(1), BetKind::AnyTriple, BetKind::Big] {
+            assert_eq!(kind.outcome_mask(D6, 2).unwrap_err(), ErrorCode::InvalidGuess.into());
+        }
+        for kind in [BetKind::Sum(3), BetKind::Sum(18), BetKind::Double(7), BetKind::Triple(0)] {
+            assert_eq!(kind.outcome_mask(D6, 3).unwrap_err(), ErrorCode::InvalidGuess.into());
+        }
+    }
+
+    #[test]
+    fn extra_dice_are_independent_of_the_first() {
+        const ROLLS: u64 = 360_000;
+        let mut counts = [[0u64; D6 as usize]; D6 as usize];
+        for i in 0..ROLLS {
+            let dice = roll_dice(&seed(i), D6, 2);
+            assert_eq!(dice[0], roll_die(&seed(i), D6));
+            assert_eq!(dice[2], 0);
+            counts[(dice[0] - 1) as usize][(dice[1] - 1) as usize] += 1;
+        }
+
+        // Each pair expects 10_000 hits, same band as the single die test
+        let expected = ROLLS / (D6 as u64 * D6 as u64);
+        for (first, row) in counts.iter().enumerate() {
+            for (second, count) in row.iter().enumerate() {
+                assert!(
+                    count.abs_diff(expected) * 100 <= expected * 3,
+                    "pair ({}, {}) hit {} times, expected about {}",
+                    first + 1,
+                    second + 1,
+                    count,
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -5,14 +5,14 @@ use anchor_lang::prelude::*;
 #[error_code]
 pub enum ErrorCode {
     // --- Bet-related Errors ---
-    #[msg("Invalid guess. Must be between 1 and 6.")]
+    #[msg("Invalid bet. Faces must be on the table's die and the bet must be able to both win and lose.")]
     InvalidGuess,
     #[msg("Bet amount exceeds maximum allowed.")]
     BetTooLarge,
     #[msg("Bet amount is below minimum allowed.")]
     BetTooSmall,
-    #[msg("Bet already placed for this roll.")]
-    AlreadyBet,
+    #[msg("Betting is closed, randomness has been committed to the roll.")]
+    BettingClosed,
     #[msg("Cannot cancel bet, roll has already been settled.")]
     RollAlreadySettled,
     #[msg("You have unclaimed winnings from a previous bet that must be settled first.")]
@@ -23,6 +23,12 @@ pub enum ErrorCode {
     InvalidPreviousRollAccount,
     #[msg("Already claimed winnings.")]
     AlreadyClaimed,
+    #[msg("Pool tables only take bets on a single face.")]
+    InvalidPoolBet,
+    #[msg("Bet would take the player's stake on this roll over the table's cap.")]
+    RollStakeLimitExceeded,
+    #[msg("Bet still has winnings to claim, only claim_winnings can close it.")]
+    BetHasWinnings,
 
     // --- Roll-related Errors ---
     #[msg("Randomness not yet revealed.")]
@@ -33,14 +39,124 @@ pub enum ErrorCode {
     RandomnessNotResolved,
     #[msg("Insufficient treasury funds to trigger a new roll. Pot needs more SOL.")]
     InsufficientTreasuryForRoll,
+    #[msg("Invalid randomness account. Expected a Switchboard On-Demand randomness account.")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness commit is stale. Commit a new seed in the previous slot.")]
+    RandomnessExpired,
+    #[msg("Randomness has been revealed for this roll, settle it instead.")]
+    RandomnessAlreadyRevealed,
+    #[msg("Randomness has already been committed to this roll.")]
+    RandomnessAlreadyCommitted,
+    #[msg("Roll has been voided after the oracle failed to reveal in time.")]
+    RollVoided,
+    #[msg("Roll has not been voided, bets cannot be refunded.")]
+    RollNotVoided,
+    #[msg("Roll has no committed randomness or has not reached its reveal deadline yet.")]
+    RollNotExpired,
+    #[msg("Roll still has open bets and cannot be closed.")]
+    RollHasOpenBets,
+    #[msg("The table's current round is still taking bets.")]
+    RoundStillOpen,
+    #[msg("Current round account does not match the table's current round.")]
+    InvalidCurrentRound,
 
     // --- Treasury/Funds Errors ---
     #[msg("Insufficient treasury funds to cover payout.")]
     InsufficientTreasury,
+    #[msg("Bet rejected, the treasury could not cover the roll's worst-case payout.")]
+    ExposureLimitExceeded,
     #[msg("Unauthorized withdrawal from treasury.")]
     UnauthorizedWithdraw,
+    #[msg("Signer is neither the table's authority nor its operator.")]
+    UnauthorizedOperator,
+    #[msg("Signer is not the table's pending authority.")]
+    NotPendingAuthority,
     #[msg("Insufficient treasury funds for withdrawal. Cannot withdraw below minimum pot.")]
     InsufficientTreasuryForWithdrawal,
+    #[msg("A treasury withdrawal is already queued.")]
+    WithdrawalAlreadyQueued,
+    #[msg("No treasury withdrawal is queued.")]
+    NoQueuedWithdrawal,
+    #[msg("Queued withdrawal is still timelocked.")]
+    WithdrawalTimelocked,
+    #[msg("Insufficient vault balance.")]
+    InsufficientVaultBalance,
+
+    // --- Session Errors ---
+    #[msg("Signer is neither the player nor one of the player's session keys.")]
+    InvalidSessionSigner,
+    #[msg("Session key has expired.")]
+    SessionExpired,
+    #[msg("Session key is not allowed to run this instruction.")]
+    SessionInstructionNotAllowed,
+    #[msg("Bet exceeds the session's remaining stake limit.")]
+    SessionStakeLimitExceeded,
+    #[msg("Session bets must be staked from the player's vault.")]
+    SessionRequiresVault,
+    #[msg("Session expiry must be in the future and the session must allow at least one instruction.")]
+    InvalidSessionParams,
+
+    // --- Jackpot Errors ---
+    #[msg("Jackpot needs a contribution of at most 10000 bps, a non-zero cap and a reachable award condition.")]
+    InvalidJackpotConfig,
+    #[msg("Jackpot account is missing or does not belong to this table.")]
+    InvalidJackpotAccount,
+    #[msg("This bet did not win a jackpot.")]
+    NoJackpotAward,
+    #[msg("Jackpot share already claimed.")]
+    JackpotAlreadyClaimed,
+
+    // --- Liquidity Errors ---
+    #[msg("Share mint does not belong to this table's bankroll.")]
+    InvalidShareMint,
+    #[msg("Bankroll has shares outstanding but nothing left behind them.")]
+    BankrollInsolvent,
+    #[msg("Deposit or withdrawal is too small to be worth a share.")]
+    ZeroShares,
+    #[msg("Not enough shares for this withdrawal.")]
+    InsufficientShares,
+    #[msg("Liquidity withdrawal is still in its cooldown.")]
+    WithdrawalCoolingDown,
+    #[msg("Liquidity withdrawal window has passed, request it again.")]
+    WithdrawalExpired,
+    #[msg("Shares can't be priced while a committed roll is waiting to settle.")]
+    RollInFlight,
+    #[msg("Treasury is backed by share holders, redeem shares instead of withdrawing.")]
+    TreasuryHasShareholders,
+    #[msg("Share cooldown can't be shorter than the treasury withdrawal delay.")]
+    ShareCooldownTooShort,
+
+    // --- Pause Errors ---
+    #[msg("This instruction is paused on the table.")]
+    ProgramPaused,
+    #[msg("Signer is neither the table's authority nor its guardian.")]
+    UnauthorizedGuardian,
+    #[msg("Pause flags must name at least one known instruction.")]
+    InvalidPauseFlags,
+
+    // --- Config Errors ---
+    #[msg("Bet limits are invalid. Minimum must be non-zero, not above the maximum, and the maximum not above the per-roll cap.")]
+    InvalidBetLimits,
+    #[msg("Die must have between 4 and 100 sides.")]
+    InvalidDieSides,
+    #[msg("A roll throws 1 to 3 dice, and multi-dice tables must use six-sided dice.")]
+    InvalidDiceCount,
+    #[msg("Pool tables must roll a single die.")]
+    InvalidGameMode,
+    #[msg("Commission cannot exceed 10000 basis points.")]
+    InvalidCommissionBps,
+    #[msg("Reveal timeout must be at least one slot.")]
+    InvalidRevealTimeout,
+    #[msg("Withdrawal delay can only be raised.")]
+    WithdrawalDelayLowered,
+
+    // --- Token Errors ---
+    #[msg("Mint does not match the game's configured mint.")]
+    InvalidMint,
+    #[msg("Token account does not belong to the expected owner or treasury.")]
+    InvalidTokenAccount,
+    #[msg("Token accounts are required for games played with an SPL mint.")]
+    MissingTokenAccounts,
 
     // --- Arithmetic Errors ---
     #[msg("Arithmetic overflow occurred.")]
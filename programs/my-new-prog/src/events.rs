@@ -2,15 +2,20 @@
 
 use anchor_lang::prelude::*; // Brings in the #[event] macro and Pubkey type
 
+use crate::{BetKind, GameConfig};
+use crate::dice::MAX_DICE;
+
 #[event]
 pub struct BetPlaced {
     pub user: Pubkey,
+    pub kind: BetKind,
     pub amount: u64,
 }
 
 #[event]
 pub struct BetCancelled {
     pub user: Pubkey,
+    pub amount: u64, // Amount refunded from the treasury
 }
 
 #[event]
@@ -19,19 +24,198 @@ pub struct WinningsClaimed {
     pub amount: u64, // Amount net received by player (after commission)
 }
 
+#[event]
+pub struct RoundOpened {
+    pub user: Pubkey, // The operator who opened the round for betting
+    pub table: Pubkey,
+    pub round_id: u64,
+}
+
 #[event]
 pub struct DieRollTriggered {
     pub user: Pubkey, // The user who initiated the roll (e.g., the game operator)
+    pub table: Pubkey,
+    pub round_id: u64,
 }
 
 #[event]
 pub struct TreasuryWithdrawn {
     pub user: Pubkey, // The authority who performed the withdrawal
+    pub table: Pubkey,
     pub amount: u64,
 }
 
 #[event]
 pub struct DieRollRevealed {
-    pub result: u8,
+    pub table: Pubkey,
+    pub round_id: u64,
+    pub result: [u8; MAX_DICE], // One face per die thrown, unused dice are zero
     pub randomness: [u8; 32], // Raw 32-byte randomness from Switchboard
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub user: Pubkey, // The authority who changed the config
+    pub table: Pubkey,
+    pub config: GameConfig,
+}
+
+#[event]
+pub struct RollVoided {
+    pub user: Pubkey, // Whoever voided the roll once its deadline passed
+    pub roll: Pubkey,
+    pub total_bets_amount: u64, // Stakes that are now refundable
+}
+
+#[event]
+pub struct BetRefunded {
+    pub user: Pubkey,
+    pub amount: u64, // Stake returned in full from a voided roll
+}
+
+#[event]
+pub struct VaultDeposited {
+    pub user: Pubkey,
+    pub amount: u64, // Amount credited to the vault
+    pub balance: u64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub user: Pubkey, // The player who delegated to the session key
+    pub session_key: Pubkey,
+    pub expiry_slot: u64,
+    pub max_stake: u64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+}
+
+#[event]
+pub struct PoolSettled {
+    pub table: Pubkey,
+    pub round_id: u64,
+    pub winning_pool: u64, // Stakes on the winning face, zero if nobody won
+    pub prize: u64,        // Split among the winning face after rake
+    pub rollover: u64,     // Carried to the next roll when nobody won
+}
+
+#[event]
+pub struct JackpotContributed {
+    pub table: Pubkey,
+    pub amount: u64, // Moved from the house into the pot by this bet
+    pub pot: u64,
+}
+
+#[event]
+pub struct JackpotAwarded {
+    pub table: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub winning_stake: u64, // Stakes the award is split across
+}
+
+#[event]
+pub struct JackpotClaimed {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ShareMintCreated {
+    pub table: Pubkey,
+    pub share_mint: Pubkey,
+    pub seed_shares: u64, // Issued to the authority for the house funds already in the treasury
+}
+
+#[event]
+pub struct LiquidityProvided {
+    pub user: Pubkey,
+    pub amount: u64, // Amount that arrived in the treasury
+    pub shares: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawalRequested {
+    pub user: Pubkey,
+    pub shares: u64,
+    pub unlock_slot: u64,
+    pub expiry_slot: u64, // Last slot the withdrawal can run in
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub user: Pubkey,
+    pub shares: u64, // Burned
+    pub amount: u64, // Paid out at the bankroll's value when the withdrawal ran
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub table: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey, // Pubkey::default() when a proposal is withdrawn
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub table: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OperatorUpdated {
+    pub table: Pubkey,
+    pub operator: Pubkey, // Pubkey::default() when the operator is removed
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub table: Pubkey,
+    pub guardian: Pubkey, // Pubkey::default() when the guardian is removed
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub user: Pubkey,
+    pub paused: u8, // Pause flags in force after the change
+}
+
+#[event]
+pub struct WithdrawalQueued {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub unlock_slot: u64, // First slot the withdrawal can be executed in
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub user: Pubkey, // The guardian or authority that cancelled it
+    pub amount: u64,
+}
+
+#[event]
+pub struct BetClosed {
+    pub user: Pubkey, // The player, who receives the bet account's rent
+    pub table: Pubkey,
+    pub round_id: u64,
+}
+
+#[event]
+pub struct RollClosed {
+    pub table: Pubkey,
+    pub round_id: u64,
+    pub payer: Pubkey, // Receives the roll account's rent
+    pub dust: u64,     // Rounding left over from the roll's payouts, returned to the house
+}
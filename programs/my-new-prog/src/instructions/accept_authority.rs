@@ -0,0 +1,36 @@
+// programs/sb_randomness/src/instructions/accept_authority.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::errors::ErrorCode;
+use crate::events::AuthorityTransferred;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.pending_authority == new_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the accept_authority instruction.
+// The table and treasury addresses derive from the creator, so they stay put.
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let previous_authority = table.authority;
+    table.authority = ctx.accounts.new_authority.key();
+    table.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        table: table.key(),
+        previous_authority,
+        authority: table.authority,
+    });
+
+    Ok(())
+}
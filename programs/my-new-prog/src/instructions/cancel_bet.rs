@@ -0,0 +1,99 @@
+// programs/sb_randomness/src/instructions/cancel_bet.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{PlayerProfile, PlayerVault, Table, TreasuryAccount, RollState, BetState};
+use crate::errors::ErrorCode;
+use crate::events::BetCancelled;
+use crate::ledger;
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct CancelBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref(), bet_state.nonce.to_le_bytes().as_ref()],
+        bump = bet_state.bump
+    )]
+    pub bet_state: Account<'info, BetState>,
+    #[account(
+        mut,
+        seeds = [b"profile", table.key().as_ref(), player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Refund into the player's vault instead of their wallet
+    #[account(
+        mut,
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump = player_vault.bump
+    )]
+    pub player_vault: Option<Account<'info, PlayerVault>>,
+}
+
+// Handler function for the cancel_bet instruction
+pub fn handler(ctx: Context<CancelBet>) -> Result<()> {
+    require!(!ctx.accounts.roll_state.revealed, ErrorCode::RollAlreadySettled);
+    require!(!ctx.accounts.roll_state.voided, ErrorCode::RollVoided);
+
+    // Once the seed is fixed, a cancel would let whoever sees it first back out of a losing bet
+    require!(!ctx.accounts.roll_state.is_committed(), ErrorCode::BettingClosed);
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.player.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let amount = ledger::refund_bet(
+        &treasury,
+        &mut ctx.accounts.table,
+        &mut ctx.accounts.roll_state,
+        &mut ctx.accounts.player_profile,
+        &ctx.accounts.bet_state,
+        ctx.accounts.player_vault.as_deref_mut(),
+        &ctx.accounts.player.to_account_info(),
+    )?;
+
+    // The bet account itself is closed to the player by the `close` constraint
+    emit!(BetCancelled {
+        user: ctx.accounts.player.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// programs/sb_randomness/src/instructions/cancel_withdrawal.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::errors::ErrorCode;
+use crate::events::WithdrawalCancelled;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.can_pause(signer.key) @ ErrorCode::UnauthorizedGuardian
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the cancel_withdrawal instruction.
+// The guardian or the authority can drop a queued withdrawal before it runs.
+pub fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let amount = table.queued_withdrawal;
+    require!(amount > 0, ErrorCode::NoQueuedWithdrawal);
+
+    table.queued_withdrawal = 0;
+    table.withdrawal_unlock_slot = 0;
+
+    emit!(WithdrawalCancelled {
+        user: ctx.accounts.signer.key(),
+        amount,
+    });
+
+    Ok(())
+}
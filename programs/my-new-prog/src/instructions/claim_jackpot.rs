@@ -0,0 +1,115 @@
+// programs/sb_randomness/src/instructions/claim_jackpot.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{PlayerVault, Table, TreasuryAccount, RollState, BetState};
+use crate::dice::outcome_index;
+use crate::errors::ErrorCode;
+use crate::events::JackpotClaimed;
+use crate::{jackpot, ledger};
+use crate::treasury::Treasury;
+use crate::pause::{require_not_paused, PAUSE_CLAIM};
+
+#[derive(Accounts)]
+pub struct ClaimJackpot<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        mut,
+        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref(), bet_state.nonce.to_le_bytes().as_ref()],
+        bump = bet_state.bump
+    )]
+    pub bet_state: Account<'info, BetState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Credit the player's vault instead of paying out to their wallet
+    #[account(
+        mut,
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump = player_vault.bump
+    )]
+    pub player_vault: Option<Account<'info, PlayerVault>>,
+}
+
+// Handler function for the claim_jackpot instruction
+pub fn handler(ctx: Context<ClaimJackpot>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_CLAIM)?;
+
+    let roll_state = &ctx.accounts.roll_state;
+    let result = roll_state.result.ok_or(ErrorCode::RollNotSettled)?;
+    require!(roll_state.jackpot_award > 0, ErrorCode::NoJackpotAward);
+
+    let table = &ctx.accounts.table;
+    let bet_state = &mut ctx.accounts.bet_state;
+    require!(!bet_state.jackpot_claimed, ErrorCode::JackpotAlreadyClaimed);
+    require!(
+        bet_state.kind.wins(&result, table.sides, table.dice_count)?,
+        ErrorCode::NoJackpotAward
+    );
+    bet_state.jackpot_claimed = true;
+
+    // Every bet that won this roll backs its winning outcome, so its stakes are the split basis
+    let outcome = outcome_index(&result, table.dice_count).ok_or(ErrorCode::NoJackpotAward)?;
+    let amount = ledger::pro_rata(
+        roll_state.jackpot_award,
+        bet_state.amount,
+        roll_state.face_stakes[outcome],
+    )?;
+
+    jackpot::pay_share(&mut ctx.accounts.table, &mut ctx.accounts.roll_state, amount)?;
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.player.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    treasury.pay_player(
+        &mut ctx.accounts.table,
+        ctx.accounts.player_vault.as_deref_mut(),
+        &ctx.accounts.player.to_account_info(),
+        amount,
+    )?;
+
+    emit!(JackpotClaimed {
+        user: ctx.accounts.player.key(),
+        amount,
+    });
+
+    // The bet account goes once its winnings have been claimed as well
+    if ctx.accounts.bet_state.claimed {
+        ctx.accounts.roll_state.close_bet()?;
+        ctx.accounts.bet_state.close(ctx.accounts.player.to_account_info())?;
+    }
+
+    Ok(())
+}
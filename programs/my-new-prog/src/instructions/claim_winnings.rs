@@ -0,0 +1,130 @@
+// programs/sb_randomness/src/instructions/claim_winnings.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{PlayerVault, SessionToken, Table, TreasuryAccount, RollState, BetState};
+use crate::events::WinningsClaimed;
+use crate::ledger;
+use crate::session::{authorize_signer, SESSION_CLAIM_WINNINGS};
+use crate::treasury::Treasury;
+use crate::pause::{require_not_paused, PAUSE_CLAIM};
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    // The player, or a session key the player delegated to
+    pub signer: Signer<'info>,
+    /// CHECK: Owner of the bet and receiver of the payout, authorized against `signer` in the handler.
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        mut,
+        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref(), bet_state.nonce.to_le_bytes().as_ref()],
+        bump = bet_state.bump
+    )]
+    pub bet_state: Account<'info, BetState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Credit the player's vault instead of paying out to their wallet
+    #[account(
+        mut,
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump = player_vault.bump
+    )]
+    pub player_vault: Option<Account<'info, PlayerVault>>,
+
+    // Lets a session key sign for the player
+    #[account(
+        mut,
+        seeds = [b"session", table.key().as_ref(), player.key().as_ref(), signer.key().as_ref()],
+        bump = session_token.bump
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+}
+
+// Handler function for the claim_winnings instruction.
+// Once nothing is left to claim the bet account is closed and its rent returned to the player.
+pub fn handler(ctx: Context<ClaimWinnings>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_CLAIM)?;
+
+    authorize_signer(
+        ctx.accounts.signer.key,
+        ctx.accounts.player.key,
+        ctx.accounts.session_token.as_deref_mut(),
+        SESSION_CLAIM_WINNINGS,
+        0,
+    )?;
+
+    let net_payout = ledger::claim_bet(
+        &mut ctx.accounts.table,
+        &mut ctx.accounts.roll_state,
+        &mut ctx.accounts.bet_state,
+    )?;
+    // A jackpot share still to claim keeps the bet account open until claim_jackpot
+    let jackpot_pending = ledger::jackpot_pending(
+        &ctx.accounts.table,
+        &ctx.accounts.roll_state,
+        &ctx.accounts.bet_state,
+    )?;
+
+    if net_payout == 0 {
+        msg!(
+            "Bet on {:?} lost, roll was {:?}",
+            ctx.accounts.bet_state.kind,
+            ctx.accounts.roll_state.result
+        );
+    } else {
+        let treasury = Treasury::new(
+            &ctx.accounts.table,
+            &ctx.accounts.treasury_pda_account,
+            ctx.accounts.player.key,
+            &ctx.accounts.mint,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.player_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        treasury.pay_player(
+            &mut ctx.accounts.table,
+            ctx.accounts.player_vault.as_deref_mut(),
+            &ctx.accounts.player.to_account_info(),
+            net_payout,
+        )?;
+
+        emit!(WinningsClaimed {
+            user: ctx.accounts.player.key(),
+            amount: net_payout,
+        });
+    }
+
+    if !jackpot_pending {
+        ctx.accounts.roll_state.close_bet()?;
+        ctx.accounts.bet_state.close(ctx.accounts.player.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// programs/sb_randomness/src/instructions/close_bet.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{Table, RollState, BetState};
+use crate::errors::ErrorCode;
+use crate::events::BetClosed;
+use crate::ledger;
+
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    // Anyone can close a losing bet, so an idle player can't keep its roll open forever
+    pub signer: Signer<'info>,
+    /// CHECK: Owner of the bet, bound by the bet seeds. Receives the bet account's rent.
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref(), bet_state.nonce.to_le_bytes().as_ref()],
+        bump = bet_state.bump
+    )]
+    pub bet_state: Account<'info, BetState>,
+}
+
+// Handler function for the close_bet instruction.
+// Only a bet with nothing to pay can be closed this way, anything else goes through claim_winnings.
+pub fn handler(ctx: Context<CloseBet>) -> Result<()> {
+    let paid = ledger::claim_bet(
+        &mut ctx.accounts.table,
+        &mut ctx.accounts.roll_state,
+        &mut ctx.accounts.bet_state,
+    )?;
+    let jackpot_pending = ledger::jackpot_pending(
+        &ctx.accounts.table,
+        &ctx.accounts.roll_state,
+        &ctx.accounts.bet_state,
+    )?;
+    require!(paid == 0 && !jackpot_pending, ErrorCode::BetHasWinnings);
+
+    ctx.accounts.roll_state.close_bet()?;
+
+    // The bet account itself is closed to the player by the `close` constraint
+    emit!(BetClosed {
+        user: ctx.accounts.player.key(),
+        table: ctx.accounts.table.key(),
+        round_id: ctx.accounts.roll_state.round_id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// programs/sb_randomness/src/instructions/close_roll.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{Table, RollState};
+use crate::errors::ErrorCode;
+use crate::events::RollClosed;
+use crate::{jackpot, ledger};
+
+#[derive(Accounts)]
+pub struct CloseRoll<'info> {
+    // Anyone can close a finished roll, the rent always goes back to its payer.
+    // Only the operator can close a round that was never triggered.
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    /// CHECK: The operator who paid for the roll, bound by `has_one`.
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+}
+
+// Handler function for the close_roll instruction.
+// A roll can go once it is settled or voided and every bet on it has been closed, or
+// when it never had randomness committed and took no bets.
+// Rounding dust the roll left behind goes to the house.
+pub fn handler(ctx: Context<CloseRoll>) -> Result<()> {
+    let roll_state = &mut ctx.accounts.roll_state;
+    require!(roll_state.open_bets == 0, ErrorCode::RollHasOpenBets);
+    if !roll_state.is_committed() {
+        // A round that never got its randomness can be dropped, but only by the operator,
+        // or anyone could close a fresh round before the first bet lands
+        require!(
+            ctx.accounts.table.can_operate(ctx.accounts.signer.key),
+            ErrorCode::UnauthorizedOperator
+        );
+        // The pot it carried in waits for the next round
+        let table = &mut ctx.accounts.table;
+        table.rollover_pool = table
+            .rollover_pool
+            .checked_add(std::mem::take(&mut roll_state.carried_pool))
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        require!(roll_state.revealed || roll_state.voided, ErrorCode::RollNotSettled);
+    }
+
+    // Every winner has been paid, so what the pool and jackpot still hold for this roll is rounding dust
+    let dust = ledger::release_pool_dust(&mut ctx.accounts.table, roll_state)?
+        .checked_add(jackpot::release_dust(&mut ctx.accounts.table, roll_state)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // With the current round gone, open_round has nothing left to check the next one against
+    if ctx.accounts.table.current_round == roll_state.key() {
+        ctx.accounts.table.current_round = Pubkey::default();
+    }
+
+    emit!(RollClosed {
+        table: ctx.accounts.table.key(),
+        round_id: roll_state.round_id,
+        payer: roll_state.payer,
+        dust,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// programs/sb_randomness/src/instructions/create_jackpot.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{Jackpot, Table};
+
+#[derive(Accounts)]
+pub struct CreateJackpot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Jackpot>(), // 8 bytes for discriminator
+        seeds = [b"jackpot", table.key().as_ref()],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler function for the create_jackpot instruction
+pub fn handler(
+    ctx: Context<CreateJackpot>,
+    contribution_bps: u16,
+    cap: u64,
+    streak_target: u8,
+    award_on_triple: bool,
+) -> Result<()> {
+    let jackpot = &mut ctx.accounts.jackpot;
+    jackpot.table = ctx.accounts.table.key();
+    jackpot.pot = 0;
+    jackpot.contribution_bps = contribution_bps;
+    jackpot.cap = cap;
+    jackpot.streak_target = streak_target;
+    jackpot.award_on_triple = award_on_triple;
+    jackpot.bump = ctx.bumps.jackpot;
+    jackpot.validate(ctx.accounts.table.dice_count)?;
+
+    ctx.accounts.table.jackpot = jackpot.key();
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// programs/sb_randomness/src/instructions/create_session.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{SessionToken, Table};
+use crate::errors::ErrorCode;
+use crate::events::SessionCreated;
+use crate::session::SESSION_ALL_INSTRUCTIONS;
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + std::mem::size_of::<SessionToken>(), // 8 bytes for discriminator
+        seeds = [b"session", table.key().as_ref(), player.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler function for the create_session instruction
+pub fn handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expiry_slot: u64,
+    max_stake: u64,
+    allowed_instructions: u8,
+) -> Result<()> {
+    require!(expiry_slot > Clock::get()?.slot, ErrorCode::InvalidSessionParams);
+    require!(
+        allowed_instructions != 0 && allowed_instructions & !SESSION_ALL_INSTRUCTIONS == 0,
+        ErrorCode::InvalidSessionParams
+    );
+
+    let session_token = &mut ctx.accounts.session_token;
+    session_token.player = ctx.accounts.player.key();
+    session_token.table = ctx.accounts.table.key();
+    session_token.session_key = session_key;
+    session_token.expiry_slot = expiry_slot;
+    session_token.max_stake = max_stake;
+    session_token.staked = 0;
+    session_token.allowed_instructions = allowed_instructions;
+    session_token.bump = ctx.bumps.session_token;
+
+    emit!(SessionCreated {
+        user: ctx.accounts.player.key(),
+        session_key,
+        expiry_slot,
+        max_stake,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// programs/sb_randomness/src/instructions/create_share_mint.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{Table, TreasuryAccount};
+use crate::bankroll::{self, SHARE_DECIMALS};
+use crate::errors::ErrorCode;
+use crate::events::ShareMintCreated;
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct CreateShareMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"shares", table.key().as_ref()],
+        bump,
+        mint::decimals = SHARE_DECIMALS,
+        mint::authority = treasury_pda_account,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = share_mint,
+        associated_token::authority = authority,
+        associated_token::token_program = share_token_program
+    )]
+    pub authority_share_account: InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+// Handler function for the create_share_mint instruction.
+// The house funds already in the treasury are issued to the authority one share per unit,
+// so outside providers buy in at the same price instead of claiming the operator's bankroll.
+pub fn handler(ctx: Context<CreateShareMint>, cooldown_slots: u64) -> Result<()> {
+    // The authority's seed shares redeem through the cooldown, so it must be at least
+    // as long as the delay on a direct treasury withdrawal
+    require!(
+        cooldown_slots >= ctx.accounts.table.config.withdrawal_delay_slots,
+        ErrorCode::ShareCooldownTooShort
+    );
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.authority.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &None,
+        &None,
+    )?;
+    let seed_shares = ctx.accounts.table.house_available(treasury.available()?);
+
+    bankroll::mint_shares(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.authority_share_account,
+        &ctx.accounts.share_token_program,
+        seed_shares,
+    )?;
+
+    let table = &mut ctx.accounts.table;
+    table.share_mint = ctx.accounts.share_mint.key();
+    table.share_cooldown_slots = cooldown_slots;
+
+    emit!(ShareMintCreated {
+        table: table.key(),
+        share_mint: table.share_mint,
+        seed_shares,
+    });
+
+    Ok(())
+}
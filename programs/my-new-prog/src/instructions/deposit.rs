@@ -0,0 +1,71 @@
+// programs/sb_randomness/src/instructions/deposit.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{PlayerVault, Table, TreasuryAccount};
+use crate::events::VaultDeposited;
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<PlayerVault>(), // 8 bytes for discriminator
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_vault: Account<'info, PlayerVault>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+    pub system_program: Program<'info, System>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Handler function for the deposit instruction
+pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.player.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let received = treasury.deposit(&ctx.accounts.player, &ctx.accounts.system_program, amount)?;
+
+    let player_vault = &mut ctx.accounts.player_vault;
+    player_vault.player = ctx.accounts.player.key();
+    player_vault.table = ctx.accounts.table.key();
+    player_vault.bump = ctx.bumps.player_vault;
+    player_vault.credit(&mut ctx.accounts.table, received)?;
+
+    emit!(VaultDeposited {
+        user: ctx.accounts.player.key(),
+        amount: received,
+        balance: player_vault.balance,
+    });
+
+    Ok(())
+}
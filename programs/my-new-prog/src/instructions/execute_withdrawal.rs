@@ -0,0 +1,75 @@
+// programs/sb_randomness/src/instructions/execute_withdrawal.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{Table, TreasuryAccount};
+use crate::errors::ErrorCode;
+use crate::events::TreasuryWithdrawn;
+use crate::pause::{require_not_paused, PAUSE_WITHDRAW};
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority @ ErrorCode::UnauthorizedWithdraw
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Handler function for the execute_withdrawal instruction.
+// Bets may have come in since the withdrawal was queued, so the treasury is checked again.
+pub fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    require_not_paused(table, PAUSE_WITHDRAW)?;
+
+    let amount = table.queued_withdrawal;
+    require!(amount > 0, ErrorCode::NoQueuedWithdrawal);
+    require!(
+        Clock::get()?.slot >= table.withdrawal_unlock_slot,
+        ErrorCode::WithdrawalTimelocked
+    );
+
+    let treasury = Treasury::new(
+        table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.authority.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    table.check_withdrawal(treasury.available()?, amount)?;
+    treasury.pay(&ctx.accounts.authority.to_account_info(), amount)?;
+
+    let table = &mut ctx.accounts.table;
+    table.queued_withdrawal = 0;
+    table.withdrawal_unlock_slot = 0;
+
+    emit!(TreasuryWithdrawn {
+        user: ctx.accounts.authority.key(),
+        table: ctx.accounts.table.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,97 @@
+// Every instruction module exposes its own `handler`, which lib.rs calls by path.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod place_bet;
 // pub mod initialize_contract;
-// pub mod trigger_random_roll;
-// pub mod cancel_bet;
-// pub mod settle_roll;
-// pub mod claim_winnings;
-// pub mod withdraw_treasury;
+pub mod open_round;
+pub mod trigger_random_roll;
+pub mod cancel_bet;
+pub mod settle_roll;
+pub mod claim_winnings;
+pub mod queue_withdrawal;
+pub mod execute_withdrawal;
+pub mod cancel_withdrawal;
+pub mod update_config;
+pub mod void_roll;
+pub mod refund_bet;
+pub mod close_roll;
+pub mod close_bet;
+pub mod deposit;
+pub mod withdraw;
+pub mod create_session;
+pub mod revoke_session;
+pub mod create_jackpot;
+pub mod claim_jackpot;
+pub mod create_share_mint;
+pub mod provide_liquidity;
+pub mod request_liquidity_withdrawal;
+pub mod withdraw_liquidity;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod set_operator;
+pub mod set_guardian;
+pub mod pause;
+pub mod unpause;
+
+pub use place_bet::*;
+pub use open_round::*;
+pub use trigger_random_roll::*;
+pub use settle_roll::*;
+pub use claim_winnings::*;
+pub use cancel_bet::*;
+pub use queue_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use update_config::*;
+pub use void_roll::*;
+pub use refund_bet::*;
+pub use close_roll::*;
+pub use close_bet::*;
+pub use deposit::*;
+pub use withdraw::*;
+pub use create_session::*;
+pub use revoke_session::*;
+pub use create_jackpot::*;
+pub use claim_jackpot::*;
+pub use create_share_mint::*;
+pub use provide_liquidity::*;
+pub use request_liquidity_withdrawal::*;
+pub use withdraw_liquidity::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_operator::*;
+pub use set_guardian::*;
+pub use pause::*;
+pub use unpause::*;
 
 // initialize_contract.rs
+// open_round.rs
 // trigger_random_roll.rs
 // place_bet.rs
 // cancel_bet.rs
 // settle_roll.rs
 // claim_winnings.rs
-// withdraw_treasury.rs
\ No newline at end of file
+// queue_withdrawal.rs
+// execute_withdrawal.rs
+// cancel_withdrawal.rs
+// update_config.rs
+// void_roll.rs
+// refund_bet.rs
+// close_roll.rs
+// close_bet.rs
+// deposit.rs
+// withdraw.rs
+// create_session.rs
+// revoke_session.rs
+// create_jackpot.rs
+// claim_jackpot.rs
+// create_share_mint.rs
+// provide_liquidity.rs
+// request_liquidity_withdrawal.rs
+// withdraw_liquidity.rs
+// propose_authority.rs
+// accept_authority.rs
+// set_operator.rs
+// set_guardian.rs
+// pause.rs
+// unpause.rs
\ No newline at end of file
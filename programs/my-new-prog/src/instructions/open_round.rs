@@ -0,0 +1,169 @@
+// programs/sb_randomness/src/instructions/open_round.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{Table, TreasuryAccount, RollState};
+use crate::dice::MAX_DIE_SIDES;
+use crate::errors::ErrorCode;
+use crate::events::RoundOpened;
+use crate::treasury::Treasury;
+use crate::pause::{require_not_paused, PAUSE_TRIGGER_ROLL};
+
+#[derive(Accounts)]
+pub struct OpenRound<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.can_operate(operator.key) @ ErrorCode::UnauthorizedOperator
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + std::mem::size_of::<RollState>(), // 8 bytes for discriminator
+        seeds = [b"roll", table.key().as_ref(), table.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    // The round opened before this one, required once the table has opened a round
+    #[account(address = table.current_round @ ErrorCode::InvalidCurrentRound)]
+    pub current_round: Option<Box<Account<'info, RollState>>>,
+    #[account(
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+    pub system_program: Program<'info, System>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+// Only one round takes bets at a time, so a player's per-roll stake cap can't be
+// spread over several open rounds
+fn ensure_betting_closed(roll_state: &RollState) -> Result<()> {
+    require!(
+        roll_state.voided || roll_state.revealed || roll_state.is_committed(),
+        ErrorCode::RoundStillOpen
+    );
+    Ok(())
+}
+
+// Handler function for the open_round instruction.
+// The round takes bets until trigger_random_roll commits randomness to it.
+pub fn handler(ctx: Context<OpenRound>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_TRIGGER_ROLL)?;
+
+    if ctx.accounts.table.current_round != Pubkey::default() {
+        let current_round = ctx
+            .accounts
+            .current_round
+            .as_ref()
+            .ok_or(ErrorCode::InvalidCurrentRound)?;
+        ensure_betting_closed(current_round)?;
+    }
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.operator.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &None,
+        &None,
+    )?;
+    require!(
+        ctx.accounts.table.house_available(treasury.available()?)
+            >= ctx.accounts.table.config.min_pot_lamports,
+        ErrorCode::InsufficientTreasuryForRoll
+    );
+
+    let clock = Clock::get()?;
+
+    let table_key = ctx.accounts.table.key();
+    let table = &mut ctx.accounts.table;
+    let round_id = table.round_id;
+    table.round_id = round_id.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    table.current_round = ctx.accounts.roll_state.key();
+    // A pool nobody won is added to this roll's pot
+    let carried_pool = std::mem::take(&mut table.rollover_pool);
+
+    let roll_state = &mut ctx.accounts.roll_state;
+    roll_state.table = table_key;
+    roll_state.round_id = round_id;
+    roll_state.randomness_account = Pubkey::default();
+    roll_state.revealed = false;
+    roll_state.result = None;
+    roll_state.total_bets_amount = 0;
+    roll_state.face_liabilities = [0; MAX_DIE_SIDES as usize];
+    roll_state.total_payouts = 0;
+    roll_state.face_stakes = [0; MAX_DIE_SIDES as usize];
+    roll_state.carried_pool = carried_pool;
+    roll_state.pool_prize = 0;
+    roll_state.pool_unclaimed = 0;
+    // Bets are placed against the rake in force now, so a later config change can't reach them
+    roll_state.commission_bps = table.config.commission_bps;
+    roll_state.jackpot_award = 0;
+    roll_state.jackpot_unclaimed = 0;
+    roll_state.commit_slot = 0;
+    roll_state.expiry_slot = 0;
+    roll_state.last_bet_slot = 0;
+    roll_state.voided = false;
+    roll_state.created_slot = clock.slot;
+    roll_state.created_at = clock.unix_timestamp;
+    roll_state.settled_slot = 0;
+    roll_state.settled_at = 0;
+    roll_state.payer = ctx.accounts.operator.key();
+    roll_state.open_bets = 0;
+    roll_state.bump = ctx.bumps.roll_state;
+
+    emit!(RoundOpened {
+        user: ctx.accounts.operator.key(),
+        table: table_key,
+        round_id,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::place_bet::ensure_betting_open;
+    use crate::PlayerProfile;
+
+    #[test]
+    fn next_round_waits_for_the_commit() {
+        let mut round_a = RollState::default();
+        let err = ensure_betting_closed(&round_a).unwrap_err();
+        assert_eq!(err, ErrorCode::RoundStillOpen.into());
+
+        round_a.randomness_account = Pubkey::new_unique();
+        round_a.commit_slot = 1_000;
+        ensure_betting_closed(&round_a).unwrap();
+    }
+
+    #[test]
+    fn stake_cap_cannot_be_reset_by_switching_rounds() {
+        let (roll_a, roll_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut round_a = RollState::default();
+        let mut profile = PlayerProfile::default();
+
+        // A is bet up to the cap, B can't open while A still takes bets
+        profile.next_bet(roll_a, 500, 500).unwrap();
+        assert!(ensure_betting_closed(&round_a).is_err());
+
+        // Once B opens A is committed, so the cap starting over on B can't be carried back to A
+        round_a.randomness_account = Pubkey::new_unique();
+        round_a.commit_slot = 1_000;
+        ensure_betting_closed(&round_a).unwrap();
+        profile.next_bet(roll_b, 500, 500).unwrap();
+        let err = ensure_betting_open(&round_a).unwrap_err();
+        assert_eq!(err, ErrorCode::BettingClosed.into());
+    }
+}
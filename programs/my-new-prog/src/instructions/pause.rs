@@ -0,0 +1,36 @@
+// programs/sb_randomness/src/instructions/pause.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::errors::ErrorCode;
+use crate::events::PauseUpdated;
+use crate::pause::validate_flags;
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.can_pause(signer.key) @ ErrorCode::UnauthorizedGuardian
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the pause instruction.
+// Either the guardian or the authority can pause, only the authority can unpause.
+pub fn handler(ctx: Context<Pause>, flags: u8) -> Result<()> {
+    validate_flags(flags)?;
+
+    let table = &mut ctx.accounts.table;
+    table.paused |= flags;
+
+    emit!(PauseUpdated {
+        user: ctx.accounts.signer.key(),
+        paused: table.paused,
+    });
+
+    Ok(())
+}
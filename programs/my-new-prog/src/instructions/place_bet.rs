@@ -1,57 +1,141 @@
 // programs/sb_randomness/src/instructions/place_bet.rs
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // Import accounts and errors from your crate
-use crate::{GlobalState, TreasuryAccount, RollState, BetState};
+use crate::{BetKind, Jackpot, PlayerProfile, PlayerVault, SessionToken, Table, TreasuryAccount, RollState, BetState};
 use crate::errors::ErrorCode;
-use crate::events::BetPlaced;
-
-// Import constants from the crate root (lib.rs)
-use crate::{MIN_BET_LAMPORTS, MAX_BET_LAMPORTS};
+use crate::events::{BetPlaced, JackpotContributed};
+use crate::{ledger, pool, vault};
+use crate::session::{authorize_signer, SESSION_PLACE_BET};
+use crate::treasury::Treasury;
+use crate::pause::{require_not_paused, PAUSE_PLACE_BET};
 
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
+    // The player, or a session key the player delegated to
     #[account(mut)]
-    pub player: Signer<'info>,
-    #[account(seeds = [b"global-state"], bump = global_state.bump)]
-    pub global_state: Account<'info, GlobalState>,
-    #[account(mut, seeds = [b"roll", roll_state.randomness_account.as_ref()], bump = roll_state.bump)]
-    pub roll_state: Account<'info, RollState>,
+    pub signer: Signer<'info>,
+    /// CHECK: Owner of the bet, authorized against `signer` in the handler.
+    pub player: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + std::mem::size_of::<PlayerProfile>(), // 8 bytes for discriminator
+        seeds = [b"profile", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
     #[account(
         init,
-        payer = player,
-        space = 8 + std::mem::size_of::<BetState>(),
-        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref()],
+        payer = signer,
+        space = 8 + std::mem::size_of::<BetState>(), // 8 bytes for discriminator
+        seeds = [
+            b"bet",
+            roll_state.key().as_ref(),
+            player.key().as_ref(),
+            player_profile.bet_nonce.to_le_bytes().as_ref()
+        ],
         bump
     )]
     pub bet_state: Account<'info, BetState>,
     #[account(
         mut,
-        seeds = [b"treasury", global_state.authority.key().as_ref()],
-        bump = global_state.treasury_bump
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
     )]
     /// CHECK: This is the treasury PDA. Funds are transferred into it.
     pub treasury_pda_account: Account<'info, TreasuryAccount>,
     pub system_program: Program<'info, System>,
 
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Stake from the player's vault instead of their wallet
+    #[account(
+        mut,
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump = player_vault.bump
+    )]
+    pub player_vault: Option<Account<'info, PlayerVault>>,
+
+    // Lets a session key sign for the player
+    #[account(
+        mut,
+        seeds = [b"session", table.key().as_ref(), player.key().as_ref(), signer.key().as_ref()],
+        bump = session_token.bump
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
     #[account(has_one = player @ ErrorCode::PreviousBetDoesNotBelongToPlayer)]
     pub previous_bet_state: Option<Account<'info, BetState>>,
 
-    pub previous_roll_state: Option<Account<'info, RollState>>,
+    pub previous_roll_state: Option<Box<Account<'info, RollState>>>,
+
+    // Required once the table has a jackpot
+    #[account(
+        mut,
+        seeds = [b"jackpot", table.key().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+}
+
+// Bets are only taken before randomness is committed, while nobody, the oracle
+// and slot leaders included, can know anything about the result
+pub(crate) fn ensure_betting_open(roll_state: &RollState) -> Result<()> {
+    require!(!roll_state.voided, ErrorCode::RollVoided);
+    require!(
+        !roll_state.revealed && !roll_state.is_committed(),
+        ErrorCode::BettingClosed
+    );
+    Ok(())
 }
 
 // Handler function for the place_bet instruction
-pub fn handler(ctx: Context<PlaceBet>, guess: u8, amount: u64) -> Result<()> {
-    require!(guess >= 1 && guess <= 6, ErrorCode::InvalidGuess);
-    require!(amount >= MIN_BET_LAMPORTS, ErrorCode::BetTooSmall);
-    require!(amount <= MAX_BET_LAMPORTS, ErrorCode::BetTooLarge);
+pub fn handler(ctx: Context<PlaceBet>, kind: BetKind, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_PLACE_BET)?;
 
-    let bet_state = &mut ctx.accounts.bet_state;
-    require!(bet_state.amount == 0, ErrorCode::AlreadyBet);
+    // Reject a bet the table can't take before any funds move
+    let (sides, dice_count) = (ctx.accounts.table.sides, ctx.accounts.table.dice_count);
+    kind.outcome_mask(sides, dice_count)?;
+    if ctx.accounts.table.mode.is_pool() {
+        pool::pool_face(&kind, sides)?;
+    }
+    let config = ctx.accounts.table.config;
+    require!(amount >= config.min_bet_lamports, ErrorCode::BetTooSmall);
+    require!(amount <= config.max_bet_lamports, ErrorCode::BetTooLarge);
+
+    ensure_betting_open(&ctx.accounts.roll_state)?;
+
+    authorize_signer(
+        ctx.accounts.signer.key,
+        ctx.accounts.player.key,
+        ctx.accounts.session_token.as_deref_mut(),
+        SESSION_PLACE_BET,
+        amount,
+    )?;
 
+    // Ensure previous bet is claimed if it exists
     if let Some(previous_bet_state_account) = &ctx.accounts.previous_bet_state {
         let previous_bet = previous_bet_state_account;
 
@@ -70,34 +154,136 @@ pub fn handler(ctx: Context<PlaceBet>, guess: u8, amount: u64) -> Result<()> {
         }
     }
 
+    // Transfer the stake to treasury. Token-2022 transfer fees are paid by the player,
+    // so the bet is booked at what actually arrived.
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.player.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let from_wallet = vault::take_stake(
+        ctx.accounts.player_vault.as_deref_mut(),
+        &mut ctx.accounts.table,
+        amount,
+    )?;
+    let stake = if from_wallet == 0 {
+        amount
+    } else {
+        // Only the player can spend from their own wallet
+        require_keys_eq!(
+            ctx.accounts.signer.key(),
+            ctx.accounts.player.key(),
+            ErrorCode::SessionRequiresVault
+        );
+        treasury.deposit(&ctx.accounts.signer, &ctx.accounts.system_program, from_wallet)?
+    };
+
+    // The bet account was derived from the profile's current nonce, which this advances
+    let player_profile = &mut ctx.accounts.player_profile;
+    player_profile.player = ctx.accounts.player.key();
+    player_profile.table = ctx.accounts.table.key();
+    player_profile.bump = ctx.bumps.player_profile;
+    let nonce = player_profile.next_bet(
+        ctx.accounts.roll_state.key(),
+        stake,
+        config.max_roll_stake_lamports,
+    )?;
+
+    // Set up new bet state
+    let bet_state = &mut ctx.accounts.bet_state;
     bet_state.player = ctx.accounts.player.key();
     bet_state.roll = ctx.accounts.roll_state.key();
-    bet_state.guess = guess;
-    bet_state.amount = amount;
+    bet_state.kind = kind;
+    bet_state.amount = stake;
     bet_state.claimed = false;
+    bet_state.jackpot_claimed = false;
+    bet_state.nonce = nonce;
     bet_state.bump = ctx.bumps.bet_state;
 
-    anchor_lang::solana_program::program::invoke(
-        &system_instruction::transfer(
-            ctx.accounts.player.key,
-            ctx.accounts.treasury_pda_account.key(),
-            amount,
-        ),
-        &[
-            ctx.accounts.player.to_account_info(),
-            ctx.accounts.treasury_pda_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
+    ledger::book_bet(&mut ctx.accounts.table, &mut ctx.accounts.roll_state, bet_state)?;
+    ctx.accounts.roll_state.last_bet_slot = Clock::get()?.slot;
+
+    // The house funds the jackpot out of what it takes in
+    if ctx.accounts.table.jackpot != Pubkey::default() {
+        let house_available = ctx.accounts.table.house_available(treasury.available()?);
+        let jackpot = ctx.accounts.jackpot.as_mut().ok_or(ErrorCode::InvalidJackpotAccount)?;
+        let contributed = jackpot.contribute(&mut ctx.accounts.table, stake)?;
+        require!(house_available >= contributed, ErrorCode::InsufficientTreasury);
+        emit!(JackpotContributed {
+            table: ctx.accounts.table.key(),
+            amount: contributed,
+            pot: jackpot.pot,
+        });
+    }
 
-    let roll_state = &mut ctx.accounts.roll_state;
-    roll_state.total_bets_amount = roll_state.total_bets_amount.checked_add(amount)
-        .ok_or(ErrorCode::MathOverflow)?;
+    // Checked after the jackpot contribution, which is no longer the house's to pay out with
+    if !ctx.accounts.table.mode.is_pool() {
+        let required = ledger::required_reserve(&ctx.accounts.table, &ctx.accounts.roll_state)?;
+        require!(
+            ctx.accounts.table.house_available(treasury.available()?) >= required,
+            ErrorCode::ExposureLimitExceeded
+        );
+    }
 
     emit!(BetPlaced {
         user: ctx.accounts.player.key(),
-        amount,
+        kind,
+        amount: stake,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_roll() -> RollState {
+        RollState::default()
+    }
+
+    fn committed_roll() -> RollState {
+        RollState {
+            randomness_account: Pubkey::new_unique(),
+            commit_slot: 1_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bet_before_commit_is_accepted() {
+        assert!(ensure_betting_open(&open_roll()).is_ok());
+    }
+
+    #[test]
+    fn bet_after_commit_is_rejected() {
+        // Nothing has been revealed yet, but the seed is already fixed
+        let err = ensure_betting_open(&committed_roll()).unwrap_err();
+        assert_eq!(err, ErrorCode::BettingClosed.into());
+    }
+
+    #[test]
+    fn late_bet_on_settled_roll_is_rejected() {
+        let roll_state = RollState {
+            revealed: true,
+            result: Some([3, 0, 0]),
+            ..committed_roll()
+        };
+        let err = ensure_betting_open(&roll_state).unwrap_err();
+        assert_eq!(err, ErrorCode::BettingClosed.into());
+    }
+
+    #[test]
+    fn bet_on_voided_roll_is_rejected() {
+        let roll_state = RollState {
+            voided: true,
+            ..committed_roll()
+        };
+        let err = ensure_betting_open(&roll_state).unwrap_err();
+        assert_eq!(err, ErrorCode::RollVoided.into());
+    }
+}
@@ -0,0 +1,34 @@
+// programs/sb_randomness/src/instructions/propose_authority.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::events::AuthorityProposed;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the propose_authority instruction.
+// Nothing changes until the new key accepts, so a mistyped key can be proposed over
+// or withdrawn with Pubkey::default().
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    table.pending_authority = new_authority;
+
+    emit!(AuthorityProposed {
+        table: table.key(),
+        authority: table.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// programs/sb_randomness/src/instructions/provide_liquidity.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{Table, TreasuryAccount};
+use crate::bankroll;
+use crate::errors::ErrorCode;
+use crate::events::LiquidityProvided;
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct ProvideLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+    #[account(
+        mut,
+        address = table.share_mint @ ErrorCode::InvalidShareMint
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = provider,
+        token::token_program = share_token_program
+    )]
+    pub provider_share_account: InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Handler function for the provide_liquidity instruction.
+// Shares are priced at the house's part of the treasury before the deposit arrives.
+pub fn handler(ctx: Context<ProvideLiquidity>, amount: u64) -> Result<()> {
+    // Buying in is priced the same way, so it waits for the result to be settled as well
+    bankroll::ensure_no_roll_in_flight(&ctx.accounts.table)?;
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.provider.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let nav = bankroll::nav(&ctx.accounts.table, treasury.available()?);
+    let received = treasury.deposit(&ctx.accounts.provider, &ctx.accounts.system_program, amount)?;
+
+    let shares = bankroll::shares_for_deposit(received, ctx.accounts.share_mint.supply, nav)?;
+    require!(shares > 0, ErrorCode::ZeroShares);
+    bankroll::mint_shares(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.provider_share_account,
+        &ctx.accounts.share_token_program,
+        shares,
+    )?;
+
+    emit!(LiquidityProvided {
+        user: ctx.accounts.provider.key(),
+        amount: received,
+        shares,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// programs/sb_randomness/src/instructions/queue_withdrawal.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{Table, TreasuryAccount};
+use crate::errors::ErrorCode;
+use crate::events::WithdrawalQueued;
+use crate::pause::{require_not_paused, PAUSE_WITHDRAW};
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority @ ErrorCode::UnauthorizedWithdraw
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+// Handler function for the queue_withdrawal instruction.
+// The withdrawal is announced now and can only run once the table's delay has passed,
+// giving players time to settle up before the bankroll shrinks.
+pub fn handler(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+    let table = &ctx.accounts.table;
+    require_not_paused(table, PAUSE_WITHDRAW)?;
+    require!(table.queued_withdrawal == 0, ErrorCode::WithdrawalAlreadyQueued);
+    require!(amount > 0, ErrorCode::InsufficientTreasuryForWithdrawal);
+
+    let treasury = Treasury::new(
+        table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.authority.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &None,
+        &None,
+    )?;
+    table.check_withdrawal(treasury.available()?, amount)?;
+
+    let unlock_slot = Clock::get()?
+        .slot
+        .checked_add(table.config.withdrawal_delay_slots)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let table = &mut ctx.accounts.table;
+    table.queued_withdrawal = amount;
+    table.withdrawal_unlock_slot = unlock_slot;
+
+    emit!(WithdrawalQueued {
+        user: ctx.accounts.authority.key(),
+        amount,
+        unlock_slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// programs/sb_randomness/src/instructions/request_liquidity_withdrawal.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{LiquidityWithdrawal, Table};
+use crate::bankroll::WITHDRAWAL_WINDOW_SLOTS;
+use crate::errors::ErrorCode;
+use crate::events::LiquidityWithdrawalRequested;
+
+#[derive(Accounts)]
+pub struct RequestLiquidityWithdrawal<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(address = table.share_mint @ ErrorCode::InvalidShareMint)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        token::mint = share_mint,
+        token::authority = provider,
+        token::token_program = share_token_program
+    )]
+    pub provider_share_account: InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = 8 + std::mem::size_of::<LiquidityWithdrawal>(), // 8 bytes for discriminator
+        seeds = [b"lp_withdrawal", table.key().as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Account<'info, LiquidityWithdrawal>,
+    pub system_program: Program<'info, System>,
+}
+
+// Handler function for the request_liquidity_withdrawal instruction.
+// Asking again replaces the pending request and restarts its cooldown, which is also how
+// a request whose window has passed is renewed.
+pub fn handler(ctx: Context<RequestLiquidityWithdrawal>, shares: u64) -> Result<()> {
+    require!(shares > 0, ErrorCode::ZeroShares);
+    require!(
+        ctx.accounts.provider_share_account.amount >= shares,
+        ErrorCode::InsufficientShares
+    );
+
+    let unlock_slot = Clock::get()?
+        .slot
+        .checked_add(ctx.accounts.table.share_cooldown_slots)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let expiry_slot = unlock_slot
+        .checked_add(WITHDRAWAL_WINDOW_SLOTS)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    withdrawal.provider = ctx.accounts.provider.key();
+    withdrawal.table = ctx.accounts.table.key();
+    withdrawal.shares = shares;
+    withdrawal.unlock_slot = unlock_slot;
+    withdrawal.expiry_slot = expiry_slot;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    emit!(LiquidityWithdrawalRequested {
+        user: ctx.accounts.provider.key(),
+        shares,
+        unlock_slot,
+        expiry_slot,
+    });
+
+    Ok(())
+}
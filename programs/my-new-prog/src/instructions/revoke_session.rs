@@ -0,0 +1,40 @@
+// programs/sb_randomness/src/instructions/revoke_session.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{SessionToken, Table};
+use crate::events::SessionRevoked;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            b"session",
+            table.key().as_ref(),
+            player.key().as_ref(),
+            session_token.session_key.as_ref()
+        ],
+        bump = session_token.bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+}
+
+// Handler function for the revoke_session instruction.
+// Closing the account is the revocation, the session key has nothing left to sign with.
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    emit!(SessionRevoked {
+        user: ctx.accounts.player.key(),
+        session_key: ctx.accounts.session_token.session_key,
+    });
+
+    Ok(())
+}
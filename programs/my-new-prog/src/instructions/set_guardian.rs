@@ -0,0 +1,31 @@
+// programs/sb_randomness/src/instructions/set_guardian.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::events::GuardianUpdated;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the set_guardian instruction
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    table.guardian = guardian;
+
+    emit!(GuardianUpdated {
+        table: table.key(),
+        guardian,
+    });
+
+    Ok(())
+}
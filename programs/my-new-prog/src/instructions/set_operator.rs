@@ -0,0 +1,32 @@
+// programs/sb_randomness/src/instructions/set_operator.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::events::OperatorUpdated;
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the set_operator instruction.
+// The operator can trigger and settle rolls, but not withdraw or change config.
+pub fn handler(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    table.operator = operator;
+
+    emit!(OperatorUpdated {
+        table: table.key(),
+        operator,
+    });
+
+    Ok(())
+}
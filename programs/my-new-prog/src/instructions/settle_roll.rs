@@ -0,0 +1,148 @@
+// programs/sb_randomness/src/instructions/settle_roll.rs
+
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::{Jackpot, Table, RollState};
+use crate::{jackpot, ledger};
+use crate::dice::{outcome_index, roll_dice};
+use crate::errors::ErrorCode;
+use crate::events::{DieRollRevealed, JackpotAwarded, PoolSettled};
+use crate::pause::{require_not_paused, PAUSE_SETTLE};
+
+#[derive(Accounts)]
+pub struct SettleRoll<'info> {
+    // Anyone can settle once the oracle has revealed, so the house can't sit on a losing result
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(address = roll_state.randomness_account @ ErrorCode::InvalidRandomnessAccount)]
+    /// CHECK: Bound to the roll by address, parsed as `RandomnessAccountData` in the handler.
+    pub randomness_account: AccountInfo<'info>,
+
+    // Required once the table has a jackpot
+    #[account(
+        mut,
+        seeds = [b"jackpot", table.key().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+}
+
+// Handler function for the settle_roll instruction
+pub fn handler(ctx: Context<SettleRoll>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_SETTLE)?;
+
+    require!(!ctx.accounts.roll_state.revealed, ErrorCode::AlreadySettled);
+    require!(!ctx.accounts.roll_state.voided, ErrorCode::RollVoided);
+
+    let clock = Clock::get()?;
+
+    let randomness_data = RandomnessAccountData::parse(ctx.accounts.randomness_account.data.borrow())
+        .map_err(|_| ErrorCode::InvalidRandomnessAccount)?;
+
+    // A re-commit replaces the seed the bets were placed against.
+    require!(
+        randomness_data.seed_slot == ctx.accounts.roll_state.commit_slot,
+        ErrorCode::RandomnessExpired
+    );
+    // The revealed value stays readable after the reveal slot, so settling doesn't have to
+    // land in that exact slot
+    require!(
+        ctx.accounts
+            .roll_state
+            .reveal_landed(randomness_data.seed_slot, randomness_data.reveal_slot),
+        ErrorCode::RandomnessNotResolved
+    );
+    let randomness = randomness_data.value;
+    let table = &mut ctx.accounts.table;
+    let roll_state = &mut ctx.accounts.roll_state;
+    table.committed_rolls = table.committed_rolls.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?;
+    let result = roll_dice(&randomness, table.sides, table.dice_count);
+    let rollover = ledger::settle_books(table, roll_state, result)?;
+
+    if let Some(rollover) = rollover {
+        emit!(PoolSettled {
+            table: table.key(),
+            round_id: roll_state.round_id,
+            winning_pool: roll_state.face_stakes[(result[0] - 1) as usize],
+            prize: roll_state.pool_prize,
+            rollover,
+        });
+    }
+
+    let dice_count = table.dice_count;
+    let outcome = outcome_index(&result, dice_count).ok_or(ErrorCode::MathOverflow)?;
+    jackpot::record_outcome(table, outcome as u8);
+
+    if table.jackpot != Pubkey::default() {
+        let jackpot = ctx.accounts.jackpot.as_mut().ok_or(ErrorCode::InvalidJackpotAccount)?;
+        // With nobody to pay, the pot keeps growing until a hit that has winners
+        let winning_stake = roll_state.face_stakes[outcome];
+        if jackpot.is_hit(table, &result[..dice_count as usize]) && winning_stake > 0 && jackpot.pot > 0 {
+            roll_state.jackpot_award = jackpot.award();
+            roll_state.jackpot_unclaimed = roll_state.jackpot_award;
+            table.streak_length = 0;
+
+            emit!(JackpotAwarded {
+                table: table.key(),
+                round_id: roll_state.round_id,
+                amount: roll_state.jackpot_award,
+                winning_stake,
+            });
+        }
+    }
+
+    roll_state.settled_slot = clock.slot;
+    roll_state.settled_at = clock.unix_timestamp;
+
+    emit!(DieRollRevealed {
+        table: table.key(),
+        round_id: roll_state.round_id,
+        result,
+        randomness,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT_SLOT: u64 = 1_000;
+
+    fn committed_roll() -> RollState {
+        RollState {
+            randomness_account: Pubkey::new_unique(),
+            commit_slot: COMMIT_SLOT,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reveal_counts_in_any_later_slot() {
+        let roll_state = committed_roll();
+        assert!(roll_state.reveal_landed(COMMIT_SLOT, COMMIT_SLOT + 1));
+        assert!(roll_state.reveal_landed(COMMIT_SLOT, COMMIT_SLOT + 500));
+    }
+
+    #[test]
+    fn stale_reveal_or_recommit_does_not_count() {
+        let roll_state = committed_roll();
+        // reveal_slot still points at the previous seed's reveal
+        assert!(!roll_state.reveal_landed(COMMIT_SLOT, COMMIT_SLOT - 50));
+        assert!(!roll_state.reveal_landed(COMMIT_SLOT + 10, COMMIT_SLOT + 11));
+        assert!(!RollState::default().reveal_landed(0, 1));
+    }
+}
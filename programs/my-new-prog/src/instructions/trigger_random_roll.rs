@@ -0,0 +1,78 @@
+// programs/sb_randomness/src/instructions/trigger_random_roll.rs
+
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+use switchboard_on_demand::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+
+use crate::{Table, RollState};
+use crate::errors::ErrorCode;
+use crate::events::DieRollTriggered;
+use crate::pause::{require_not_paused, PAUSE_TRIGGER_ROLL};
+
+#[derive(Accounts)]
+pub struct TriggerRandomRoll<'info> {
+    pub operator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.can_operate(operator.key) @ ErrorCode::UnauthorizedOperator
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"roll", table.key().as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(owner = SWITCHBOARD_ON_DEMAND_PROGRAM_ID @ ErrorCode::InvalidRandomnessAccount)]
+    /// CHECK: Owned by Switchboard On-Demand, parsed as `RandomnessAccountData` in the handler.
+    pub randomness_account: AccountInfo<'info>,
+}
+
+// Handler function for the trigger_random_roll instruction.
+// Binds a fresh Switchboard commit to an open round, which closes betting on it.
+pub fn handler(ctx: Context<TriggerRandomRoll>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_TRIGGER_ROLL)?;
+
+    let roll_state = &ctx.accounts.roll_state;
+    require!(!roll_state.voided, ErrorCode::RollVoided);
+    require!(!roll_state.is_committed(), ErrorCode::RandomnessAlreadyCommitted);
+
+    let clock = Clock::get()?;
+
+    let randomness_data = RandomnessAccountData::parse(ctx.accounts.randomness_account.data.borrow())
+        .map_err(|_| ErrorCode::InvalidRandomnessAccount)?;
+
+    // The commit has to land in the slot right before this one, so the operator
+    // can't pick a randomness account whose seed has been sitting around.
+    require!(
+        randomness_data.seed_slot == clock.slot.saturating_sub(1),
+        ErrorCode::RandomnessExpired
+    );
+    // Every bet must be older than the seed, or whoever sees the seed first could have bet on it
+    require!(
+        roll_state.last_bet_slot < randomness_data.seed_slot,
+        ErrorCode::RandomnessExpired
+    );
+    let commit_slot = randomness_data.seed_slot;
+    let expiry_slot = clock
+        .slot
+        .checked_add(ctx.accounts.table.config.reveal_timeout_slots)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let roll_state = &mut ctx.accounts.roll_state;
+    roll_state.randomness_account = ctx.accounts.randomness_account.key();
+    roll_state.commit_slot = commit_slot;
+    roll_state.expiry_slot = expiry_slot;
+    let table = &mut ctx.accounts.table;
+    table.committed_rolls = table.committed_rolls.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(DieRollTriggered {
+        user: ctx.accounts.operator.key(),
+        table: table.key(),
+        round_id: roll_state.round_id,
+    });
+
+    Ok(())
+}
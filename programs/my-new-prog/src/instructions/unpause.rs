@@ -0,0 +1,34 @@
+// programs/sb_randomness/src/instructions/unpause.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::events::PauseUpdated;
+use crate::pause::validate_flags;
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the unpause instruction
+pub fn handler(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+    validate_flags(flags)?;
+
+    let table = &mut ctx.accounts.table;
+    table.paused &= !flags;
+
+    emit!(PauseUpdated {
+        user: ctx.accounts.authority.key(),
+        paused: table.paused,
+    });
+
+    Ok(())
+}
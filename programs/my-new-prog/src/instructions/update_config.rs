@@ -0,0 +1,45 @@
+// programs/sb_randomness/src/instructions/update_config.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{GameConfig, Table};
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        has_one = authority
+    )]
+    pub table: Account<'info, Table>,
+}
+
+// Handler function for the update_config instruction.
+// Bets already placed keep the payout they were locked in with.
+pub fn handler(ctx: Context<UpdateConfig>, config: GameConfig) -> Result<()> {
+    config.validate()?;
+    // Players rely on the announcement window, so it can't be shortened under them
+    require!(
+        config.withdrawal_delay_slots >= ctx.accounts.table.config.withdrawal_delay_slots,
+        ErrorCode::WithdrawalDelayLowered
+    );
+
+    let table = &mut ctx.accounts.table;
+    table.config = config;
+    // A longer delay drags the share cooldown up with it, or shares would become the quick way out
+    if table.share_mint != Pubkey::default() {
+        table.share_cooldown_slots = table.share_cooldown_slots.max(config.withdrawal_delay_slots);
+    }
+
+    emit!(ConfigUpdated {
+        user: ctx.accounts.authority.key(),
+        table: table.key(),
+        config,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// programs/sb_randomness/src/instructions/void_roll.rs
+
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::{RollState, Table};
+use crate::errors::ErrorCode;
+use crate::events::RollVoided;
+
+#[derive(Accounts)]
+pub struct VoidRoll<'info> {
+    // Anyone can void a roll once the oracle has missed its deadline
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"roll", roll_state.table.as_ref(), roll_state.round_id.to_le_bytes().as_ref()],
+        bump = roll_state.bump
+    )]
+    pub roll_state: Box<Account<'info, RollState>>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump,
+        constraint = table.key() == roll_state.table
+    )]
+    pub table: Account<'info, Table>,
+    #[account(address = roll_state.randomness_account @ ErrorCode::InvalidRandomnessAccount)]
+    /// CHECK: Bound to the roll by address, parsed as `RandomnessAccountData` in the handler.
+    pub randomness_account: AccountInfo<'info>,
+}
+
+// Handler function for the void_roll instruction.
+// Only a roll the oracle never revealed can be voided. A revealed roll has to be settled,
+// otherwise whoever dislikes the result could hold the settle back and void it instead.
+pub fn handler(ctx: Context<VoidRoll>) -> Result<()> {
+    let roll_state = &mut ctx.accounts.roll_state;
+    require!(!roll_state.revealed, ErrorCode::AlreadySettled);
+    require!(!roll_state.voided, ErrorCode::RollVoided);
+    require!(roll_state.is_committed(), ErrorCode::RollNotExpired);
+    require!(
+        Clock::get()?.slot > roll_state.expiry_slot,
+        ErrorCode::RollNotExpired
+    );
+    {
+        let randomness_data = RandomnessAccountData::parse(ctx.accounts.randomness_account.data.borrow())
+            .map_err(|_| ErrorCode::InvalidRandomnessAccount)?;
+        require!(
+            !roll_state.reveal_landed(randomness_data.seed_slot, randomness_data.reveal_slot),
+            ErrorCode::RandomnessAlreadyRevealed
+        );
+    }
+
+    roll_state.voided = true;
+
+    // Bets are refunded, but a pot carried in from earlier rolls waits for the next one
+    let table = &mut ctx.accounts.table;
+    table.committed_rolls = table.committed_rolls.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?;
+    table.rollover_pool = table
+        .rollover_pool
+        .checked_add(std::mem::take(&mut roll_state.carried_pool))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(RollVoided {
+        user: ctx.accounts.caller.key(),
+        roll: roll_state.key(),
+        total_bets_amount: roll_state.total_bets_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// programs/sb_randomness/src/instructions/withdraw.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{PlayerVault, Table, TreasuryAccount};
+use crate::events::VaultWithdrawn;
+use crate::treasury::Treasury;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"vault", table.key().as_ref(), player.key().as_ref()],
+        bump = player_vault.bump
+    )]
+    pub player_vault: Account<'info, PlayerVault>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Handler function for the withdraw instruction.
+// Vault funds are always backed by the treasury, so no house balance check is needed.
+pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let player_vault = &mut ctx.accounts.player_vault;
+    player_vault.debit(&mut ctx.accounts.table, amount)?;
+
+    let treasury = Treasury::new(
+        &ctx.accounts.table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.player.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    treasury.pay(&ctx.accounts.player.to_account_info(), amount)?;
+
+    emit!(VaultWithdrawn {
+        user: ctx.accounts.player.key(),
+        amount,
+        balance: ctx.accounts.player_vault.balance,
+    });
+
+    Ok(())
+}
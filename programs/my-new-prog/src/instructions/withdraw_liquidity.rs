@@ -0,0 +1,113 @@
+// programs/sb_randomness/src/instructions/withdraw_liquidity.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::{LiquidityWithdrawal, Table, TreasuryAccount};
+use crate::bankroll;
+use crate::errors::ErrorCode;
+use crate::events::LiquidityWithdrawn;
+use crate::treasury::Treasury;
+use crate::pause::{require_not_paused, PAUSE_WITHDRAW};
+
+#[derive(Accounts)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(
+        seeds = [b"table", table.creator.as_ref(), table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [b"treasury", table.key().as_ref()],
+        bump = table.treasury_bump
+    )]
+    pub treasury_pda_account: Account<'info, TreasuryAccount>,
+    #[account(
+        mut,
+        address = table.share_mint @ ErrorCode::InvalidShareMint
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = provider,
+        token::token_program = share_token_program
+    )]
+    pub provider_share_account: InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        close = provider,
+        seeds = [b"lp_withdrawal", table.key().as_ref(), provider.key().as_ref()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, LiquidityWithdrawal>,
+
+    // Token games only, leave out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Handler function for the withdraw_liquidity instruction.
+// Shares are redeemed at the bankroll's value now, not when the withdrawal was requested,
+// and the treasury must still cover every open bet afterwards.
+pub fn handler(ctx: Context<WithdrawLiquidity>) -> Result<()> {
+    require_not_paused(&ctx.accounts.table, PAUSE_WITHDRAW)?;
+
+    let shares = ctx.accounts.withdrawal.shares;
+    ctx.accounts.withdrawal.check_window(Clock::get()?.slot)?;
+    bankroll::ensure_no_roll_in_flight(&ctx.accounts.table)?;
+    require!(
+        ctx.accounts.provider_share_account.amount >= shares,
+        ErrorCode::InsufficientShares
+    );
+
+    let table = &ctx.accounts.table;
+    let treasury = Treasury::new(
+        table,
+        &ctx.accounts.treasury_pda_account,
+        ctx.accounts.provider.key,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let treasury_available = treasury.available()?;
+    let nav = bankroll::nav(table, treasury_available);
+    let amount = bankroll::share_value(shares, ctx.accounts.share_mint.supply, nav)?;
+    require!(amount > 0, ErrorCode::ZeroShares);
+
+    // Providers are held to the same floor as the authority's treasury withdrawals
+    require!(
+        table.house_available(treasury_available) >= table.withdrawal_floor(amount)?,
+        ErrorCode::InsufficientTreasuryForWithdrawal
+    );
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.share_token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.provider_share_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+    treasury.pay(&ctx.accounts.provider.to_account_info(), amount)?;
+
+    emit!(LiquidityWithdrawn {
+        user: ctx.accounts.provider.key(),
+        shares,
+        amount,
+    });
+
+    Ok(())
+}
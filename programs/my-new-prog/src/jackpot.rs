@@ -0,0 +1,204 @@
+// programs/sb_randomness/src/jackpot.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{ledger, RollState, Table, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+/// A table's progressive jackpot, fed a slice of every bet.
+///
+/// Like vault balances, the jackpot's funds stay in the treasury. The table's
+/// `jackpot_balance` keeps them, and any award not yet claimed, away from the house.
+#[account]
+#[derive(Default)]
+pub struct Jackpot {
+    pub table: Pubkey,
+    pub pot: u64,              // Grows with every bet until awarded
+    pub contribution_bps: u16, // Share of each stake moved from the house into the pot
+    pub cap: u64,              // The pot stops growing here, the rest stays with the house
+    pub streak_target: u8,     // Award when the same outcome comes up this many rolls running, 0 to disable
+    pub award_on_triple: bool, // Award on any triple, three-dice tables only
+    pub bump: u8,
+}
+
+impl Jackpot {
+    pub fn validate(&self, dice_count: u8) -> Result<()> {
+        require!(
+            self.contribution_bps as u64 <= BPS_DENOMINATOR && self.cap > 0,
+            ErrorCode::InvalidJackpotConfig
+        );
+        require!(
+            self.streak_target >= 2 || self.award_on_triple,
+            ErrorCode::InvalidJackpotConfig
+        );
+        require!(
+            !self.award_on_triple || dice_count == 3,
+            ErrorCode::InvalidJackpotConfig
+        );
+        Ok(())
+    }
+
+    /// Moves this stake's share into the pot, up to the cap. Returns what was added.
+    pub fn contribute(&mut self, table: &mut Table, stake: u64) -> Result<u64> {
+        let share = ledger::commission_on(stake, self.contribution_bps)?;
+        let room = self.cap.saturating_sub(self.pot);
+        let amount = share.min(room);
+
+        self.pot += amount;
+        table.jackpot_balance = table
+            .jackpot_balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(amount)
+    }
+
+    /// Whether the roll that just settled wins the jackpot.
+    pub fn is_hit(&self, table: &Table, dice: &[u8]) -> bool {
+        let streak = self.streak_target >= 2 && table.streak_length >= self.streak_target;
+        let triple = self.award_on_triple && dice.len() == 3 && dice.iter().all(|d| *d == dice[0]);
+        streak || triple
+    }
+
+    /// Empties the pot into an award for the winners of the roll that hit it.
+    pub fn award(&mut self) -> u64 {
+        std::mem::take(&mut self.pot)
+    }
+}
+
+/// Tracks how many rolls in a row have come up with `outcome`.
+pub fn record_outcome(table: &mut Table, outcome: u8) {
+    if table.streak_length > 0 && table.streak_outcome == outcome {
+        table.streak_length = table.streak_length.saturating_add(1);
+    } else {
+        table.streak_outcome = outcome;
+        table.streak_length = 1;
+    }
+}
+
+/// Pays a claimed share out of the roll's award.
+pub fn pay_share(table: &mut Table, roll_state: &mut RollState, amount: u64) -> Result<()> {
+    roll_state.jackpot_unclaimed = roll_state
+        .jackpot_unclaimed
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    table.jackpot_balance = table
+        .jackpot_balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    Ok(())
+}
+
+/// Hands the rounding dust left once every share of the roll's award is claimed back to the house.
+pub fn release_dust(table: &mut Table, roll_state: &mut RollState) -> Result<u64> {
+    let dust = std::mem::take(&mut roll_state.jackpot_unclaimed);
+    table.jackpot_balance = table
+        .jackpot_balance
+        .checked_sub(dust)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    Ok(dust)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jackpot(streak_target: u8, award_on_triple: bool) -> Jackpot {
+        Jackpot {
+            contribution_bps: 100,
+            cap: 10_000,
+            streak_target,
+            award_on_triple,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn contributions_stop_at_the_cap() {
+        let mut table = Table::default();
+        let mut jackpot = jackpot(3, false);
+
+        assert_eq!(jackpot.contribute(&mut table, 500_000).unwrap(), 5_000);
+        assert_eq!(jackpot.contribute(&mut table, 400_000).unwrap(), 4_000);
+        // Only 1_000 of the next 5_000 fits, the rest stays with the house
+        assert_eq!(jackpot.contribute(&mut table, 500_000).unwrap(), 1_000);
+        assert_eq!(jackpot.contribute(&mut table, 500_000).unwrap(), 0);
+
+        assert_eq!(jackpot.pot, 10_000);
+        assert_eq!(table.jackpot_balance, 10_000);
+        assert_eq!(table.house_available(1_000_000), 990_000);
+    }
+
+    #[test]
+    fn streak_hits_after_the_target_and_resets_on_a_new_outcome() {
+        let mut table = Table::default();
+        let jackpot = jackpot(3, false);
+
+        for (outcome, hit) in [(4, false), (4, false), (2, false), (2, false), (2, true)] {
+            record_outcome(&mut table, outcome);
+            assert_eq!(jackpot.is_hit(&table, &[outcome]), hit, "after {}", outcome);
+        }
+        assert_eq!(table.streak_length, 3);
+    }
+
+    #[test]
+    fn triple_hits_only_when_enabled() {
+        let table = Table::default();
+        assert!(jackpot(0, true).is_hit(&table, &[5, 5, 5]));
+        assert!(!jackpot(0, true).is_hit(&table, &[5, 5, 4]));
+        assert!(!jackpot(3, false).is_hit(&table, &[5, 5, 5]));
+    }
+
+    #[test]
+    fn award_is_split_by_stake_among_winners() {
+        let mut jackpot = jackpot(2, false);
+        jackpot.pot = 10_000;
+        let award = jackpot.award();
+        assert_eq!(jackpot.pot, 0);
+
+        let shares: Vec<u64> = [100, 200, 400]
+            .iter()
+            .map(|stake| ledger::pro_rata(award, *stake, 700).unwrap())
+            .collect();
+        assert_eq!(shares, vec![1_428, 2_857, 5_714]);
+        assert!(shares.iter().sum::<u64>() <= award);
+    }
+
+    #[test]
+    fn award_dust_goes_to_the_house_on_close() {
+        let mut table = Table {
+            jackpot_balance: 10_000,
+            ..Default::default()
+        };
+        let mut roll_state = RollState {
+            jackpot_award: 10_000,
+            jackpot_unclaimed: 10_000,
+            ..Default::default()
+        };
+        for share in [1_428, 2_857, 5_714] {
+            pay_share(&mut table, &mut roll_state, share).unwrap();
+        }
+        assert_eq!(table.jackpot_balance, 1);
+
+        assert_eq!(release_dust(&mut table, &mut roll_state).unwrap(), 1);
+        assert_eq!(table.jackpot_balance, 0);
+        assert_eq!(table.house_available(1_000_000), 1_000_000);
+    }
+
+    #[test]
+    fn jackpot_config_needs_a_reachable_condition() {
+        assert_eq!(
+            jackpot(0, false).validate(1).unwrap_err(),
+            ErrorCode::InvalidJackpotConfig.into()
+        );
+        assert_eq!(
+            jackpot(1, false).validate(1).unwrap_err(),
+            ErrorCode::InvalidJackpotConfig.into()
+        );
+        assert_eq!(
+            jackpot(0, true).validate(2).unwrap_err(),
+            ErrorCode::InvalidJackpotConfig.into()
+        );
+        jackpot(3, false).validate(1).unwrap();
+        jackpot(0, true).validate(3).unwrap();
+    }
+}
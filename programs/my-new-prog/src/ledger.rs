@@ -0,0 +1,508 @@
+// programs/sb_randomness/src/ledger.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{BetState, GameMode, PlayerProfile, PlayerVault, RollState, Table, BPS_DENOMINATOR};
+use crate::dice::{outcome_index, MAX_DICE};
+use crate::errors::ErrorCode;
+use crate::pool::{self, PoolOutcome};
+use crate::treasury::Treasury;
+
+// The table and roll bookkeeping behind place_bet, settle_roll, claim_winnings,
+// cancel_bet and refund_bet.
+// Handlers move the money, these functions only move the numbers, so tests can run
+// the same books the instructions do.
+
+/// Books `bet_state`'s stake on the roll under its kind. Fixed-odds bets reserve their
+/// gross payout and lock in the commission owed on it, pool bets join the pot.
+pub fn book_bet(table: &mut Table, roll_state: &mut RollState, bet_state: &mut BetState) -> Result<()> {
+    let (sides, dice_count) = (table.sides, table.dice_count);
+    let stake = bet_state.amount;
+    if table.mode.is_pool() {
+        // Pool winners are paid from the stakes, so the house carries no exposure
+        let face = pool::pool_face(&bet_state.kind, sides)?;
+        pool::add_stake(table, roll_state, face, stake)?;
+        bet_state.payout = 0;
+        bet_state.commission = 0;
+    } else {
+        let mask = bet_state.kind.outcome_mask(sides, dice_count)?;
+        let payout = bet_state.kind.gross_payout(stake, sides, dice_count)?;
+        roll_state.add_liability(mask, payout, stake)?;
+        table.outstanding_liabilities = table
+            .outstanding_liabilities
+            .checked_add(payout)
+            .ok_or(ErrorCode::MathOverflow)?;
+        bet_state.payout = payout;
+        bet_state.commission = commission_on(payout, table.config.commission_bps)?;
+    }
+
+    roll_state.total_bets_amount = roll_state
+        .total_bets_amount
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    roll_state.open_bets = roll_state.open_bets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// `bps` basis points of `amount`, rounded down. Commission, pool rake and jackpot
+/// contributions are all taken this way.
+pub fn commission_on(amount: u64, bps: u16) -> Result<u64> {
+    let commission = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(commission).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// The cut of `amount` that `stake` earns out of `total` staked, rounded down.
+/// Pool prizes and jackpot awards are split this way, the rounding dust stays reserved
+/// until the roll is closed.
+pub fn pro_rata(amount: u64, stake: u64, total: u64) -> Result<u64> {
+    require!(total > 0, ErrorCode::MathUnderflow);
+    let share = (amount as u128)
+        .checked_mul(stake as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / total as u128;
+    u64::try_from(share).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// House funds a fixed-odds table must hold once a bet is booked on `roll_state`.
+/// Only one outcome can win, so that is every other roll plus this roll's largest outcome.
+pub fn required_reserve(table: &Table, roll_state: &RollState) -> Result<u64> {
+    table
+        .outstanding_liabilities
+        .checked_sub(roll_state.total_payouts)
+        .ok_or(ErrorCode::MathUnderflow)?
+        .checked_add(roll_state.worst_case_liability())
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Takes a cancelled or refunded bet back off the roll's books and frees its stake under
+/// the player's per-roll cap. Returns the stake owed back to the player.
+pub fn remove_bet(
+    table: &mut Table,
+    roll_state: &mut RollState,
+    player_profile: &mut PlayerProfile,
+    bet_state: &BetState,
+) -> Result<u64> {
+    let amount = bet_state.amount;
+    roll_state.total_bets_amount = roll_state
+        .total_bets_amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    if table.mode.is_pool() {
+        let face = pool::pool_face(&bet_state.kind, table.sides)?;
+        pool::remove_stake(table, roll_state, face, amount)?;
+    } else {
+        let mask = bet_state.kind.outcome_mask(table.sides, table.dice_count)?;
+        roll_state.remove_liability(mask, bet_state.payout, amount)?;
+        table.outstanding_liabilities = table
+            .outstanding_liabilities
+            .checked_sub(bet_state.payout)
+            .ok_or(ErrorCode::MathUnderflow)?;
+    }
+    roll_state.close_bet()?;
+    player_profile.release_stake(bet_state.roll, amount);
+    Ok(amount)
+}
+
+/// Takes the bet off the roll's books and pays its stake back to the player's vault or wallet.
+/// The books move first, so a pool stake is no longer held back from the refund.
+pub fn refund_bet<'info>(
+    treasury: &Treasury<'_, 'info>,
+    table: &mut Table,
+    roll_state: &mut RollState,
+    player_profile: &mut PlayerProfile,
+    bet_state: &BetState,
+    player_vault: Option<&mut PlayerVault>,
+    player: &AccountInfo<'info>,
+) -> Result<u64> {
+    let amount = remove_bet(table, roll_state, player_profile, bet_state)?;
+    treasury.pay_player(table, player_vault, player, amount)?;
+    Ok(amount)
+}
+
+/// Settles the roll's books on the thrown dice.
+/// Returns the pot carried to the next roll for pool tables, `None` for fixed odds.
+pub fn settle_books(table: &mut Table, roll_state: &mut RollState, result: [u8; MAX_DICE]) -> Result<Option<u64>> {
+    roll_state.revealed = true;
+    roll_state.result = Some(result);
+
+    if !table.mode.is_pool() {
+        // Only the bets on the thrown outcome can still be paid, the rest of the roll's
+        // reserve goes back to the house now rather than waiting on losers to claim
+        let outcome = outcome_index(&result, table.dice_count).ok_or(ErrorCode::MathOverflow)?;
+        let released = roll_state
+            .total_payouts
+            .checked_sub(roll_state.face_liabilities[outcome])
+            .ok_or(ErrorCode::MathUnderflow)?;
+        table.outstanding_liabilities = table
+            .outstanding_liabilities
+            .checked_sub(released)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        // What is left is now owed to the winners, it no longer belongs to the bankroll
+        table.settled_liabilities = table
+            .settled_liabilities
+            .checked_add(roll_state.face_liabilities[outcome])
+            .ok_or(ErrorCode::MathOverflow)?;
+        return Ok(None);
+    }
+
+    let pot = roll_state
+        .total_bets_amount
+        .checked_add(roll_state.carried_pool)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let winning_pool = roll_state.face_stakes[(result[0] - 1) as usize];
+    let mut rollover = 0;
+    match pool::settle_pool(table.mode, pot, winning_pool, roll_state.commission_bps)? {
+        PoolOutcome::Winners { prize, rake } => {
+            roll_state.pool_prize = prize;
+            roll_state.pool_unclaimed = prize;
+            pool::release(table, rake)?;
+        }
+        PoolOutcome::Rollover(amount) => {
+            table.rollover_pool = table
+                .rollover_pool
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            rollover = amount;
+        }
+        PoolOutcome::Refund => roll_state.pool_unclaimed = pot,
+    }
+    Ok(Some(rollover))
+}
+
+/// Marks a bet on a settled roll claimed and takes it off the books.
+/// Returns what the bet is paid, zero for a loss.
+pub fn claim_bet(table: &mut Table, roll_state: &mut RollState, bet_state: &mut BetState) -> Result<u64> {
+    require!(roll_state.revealed, ErrorCode::RollNotSettled);
+    let result = roll_state.result.ok_or(ErrorCode::RollNotSettled)?;
+    require!(!bet_state.claimed, ErrorCode::AlreadyClaimed);
+
+    // Losing bets are only marked resolved, so they stop blocking the next place_bet.
+    bet_state.claimed = true;
+
+    let won = bet_state.kind.wins(&result, table.sides, table.dice_count)?;
+    if table.mode.is_pool() {
+        // The rake was taken from the pot on settle, winners split what is left
+        let winning_pool = roll_state.face_stakes[(result[0] - 1) as usize];
+        let amount = if winning_pool == 0 && table.mode == GameMode::PoolRefund {
+            bet_state.amount
+        } else if won {
+            pro_rata(roll_state.pool_prize, bet_state.amount, winning_pool)?
+        } else {
+            0
+        };
+        pool::release(table, amount)?;
+        roll_state.pool_unclaimed = roll_state
+            .pool_unclaimed
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        Ok(amount)
+    } else if won {
+        // A loser's reserve was already released when the roll settled
+        table.outstanding_liabilities = table
+            .outstanding_liabilities
+            .checked_sub(bet_state.payout)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        table.settled_liabilities = table
+            .settled_liabilities
+            .checked_sub(bet_state.payout)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        Ok(bet_state
+            .payout
+            .checked_sub(bet_state.commission)
+            .ok_or(ErrorCode::MathUnderflow)?)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Hands the rounding dust left once every pool bet on the roll is paid back to the house.
+pub fn release_pool_dust(table: &mut Table, roll_state: &mut RollState) -> Result<u64> {
+    let dust = std::mem::take(&mut roll_state.pool_unclaimed);
+    pool::release(table, dust)?;
+    Ok(dust)
+}
+
+/// Whether the bet still has a jackpot share to claim, which keeps its account open.
+pub fn jackpot_pending(table: &Table, roll_state: &RollState, bet_state: &BetState) -> Result<bool> {
+    let Some(result) = roll_state.result else {
+        return Ok(false);
+    };
+    Ok(roll_state.jackpot_award > 0
+        && !bet_state.jackpot_claimed
+        && bet_state.kind.wins(&result, table.sides, table.dice_count)?)
+}
+
+/// A table driven through the same vault and ledger functions the instructions call.
+/// Only the token movements the handlers make by CPI are simulated here.
+#[cfg(test)]
+pub(crate) mod harness {
+    use super::*;
+    use crate::vault::{credit_payout, take_stake};
+    use crate::{BetKind, PlayerVault};
+
+    /// A bet of `amount` on `kind` as place_bet sets it up, before it is booked.
+    pub(crate) fn unbooked_bet(kind: BetKind, amount: u64) -> BetState {
+        BetState {
+            player: Pubkey::default(),
+            roll: Pubkey::default(),
+            kind,
+            amount,
+            claimed: false,
+            payout: 0,
+            commission: 0,
+            jackpot_claimed: false,
+            nonce: 0,
+            bump: 0,
+        }
+    }
+
+    pub(crate) struct Game {
+        pub table: Table,
+        pub roll: RollState,
+        pub vaults: Vec<PlayerVault>,
+        bets: Vec<(usize, bool, BetState)>, // Player, staked from their vault, the bet
+        treasury: u64,
+        // The house's side of the money flows, tallied apart from the table's books
+        funding: u64,
+        staked: u64,
+        paid: u64,
+    }
+
+    impl Game {
+        pub fn new(mode: GameMode, commission_bps: u16, players: usize, funding: u64) -> Self {
+            let mut table = Table {
+                sides: 6,
+                dice_count: 1,
+                mode,
+                ..Default::default()
+            };
+            table.config.commission_bps = commission_bps;
+            Game {
+                table,
+                roll: RollState {
+                    commission_bps,
+                    ..Default::default()
+                },
+                vaults: (0..players).map(|_| PlayerVault::default()).collect(),
+                bets: Vec::new(),
+                treasury: funding,
+                funding,
+                staked: 0,
+                paid: 0,
+            }
+        }
+
+        pub fn deposit(&mut self, player: usize, amount: u64) {
+            self.treasury += amount;
+            self.vaults[player].credit(&mut self.table, amount).unwrap();
+        }
+
+        pub fn withdraw(&mut self, player: usize, amount: u64) -> Result<()> {
+            self.vaults[player].debit(&mut self.table, amount)?;
+            self.treasury -= amount;
+            Ok(())
+        }
+
+        /// A bet paid for from the player's wallet.
+        pub fn bet(&mut self, player: usize, face: u8, stake: u64) -> Result<()> {
+            self.place(player, false, face, stake)
+        }
+
+        /// A bet paid for from the player's vault.
+        pub fn bet_from_vault(&mut self, player: usize, face: u8, stake: u64) -> Result<()> {
+            self.place(player, true, face, stake)
+        }
+
+        fn place(&mut self, player: usize, from_vault: bool, face: u8, stake: u64) -> Result<()> {
+            let player_vault = from_vault.then(|| &mut self.vaults[player]);
+            self.treasury += take_stake(player_vault, &mut self.table, stake)?;
+            let mut bet_state = unbooked_bet(BetKind::Exact(face), stake);
+            book_bet(&mut self.table, &mut self.roll, &mut bet_state)?;
+            self.staked += stake;
+            self.bets.push((player, from_vault, bet_state));
+            Ok(())
+        }
+
+        /// Settles on `face` and pays every bet back where its stake came from,
+        /// returning what each one was paid.
+        pub fn settle_and_claim(&mut self, face: u8) -> Vec<u64> {
+            settle_books(&mut self.table, &mut self.roll, [face, 0, 0]).unwrap();
+            let mut bets = std::mem::take(&mut self.bets);
+            let paid = bets
+                .iter_mut()
+                .map(|(player, from_vault, bet_state)| {
+                    let paid = claim_bet(&mut self.table, &mut self.roll, bet_state).unwrap();
+                    let player_vault = from_vault.then(|| &mut self.vaults[*player]);
+                    self.treasury -= credit_payout(player_vault, &mut self.table, paid).unwrap();
+                    self.paid += paid;
+                    paid
+                })
+                .collect();
+            self.bets = bets;
+            paid
+        }
+
+        /// Opens the next round the way open_round does.
+        pub fn next_roll(&mut self) {
+            self.roll = RollState {
+                carried_pool: std::mem::take(&mut self.table.rollover_pool),
+                commission_bps: self.table.config.commission_bps,
+                ..Default::default()
+            };
+            self.bets.clear();
+        }
+
+        /// Closes the round the way close_roll does, returning the dust handed to the house.
+        pub fn close_roll(&mut self) -> u64 {
+            release_pool_dust(&mut self.table, &mut self.roll).unwrap()
+        }
+
+        /// What the table's books leave to the house out of the treasury.
+        pub fn house_available(&self) -> u64 {
+            self.table.house_available(self.treasury)
+        }
+
+        /// The house's funding plus every stake taken, less every payout made.
+        /// Pool money counts here until it is paid out.
+        pub fn house_equity(&self) -> u64 {
+            self.funding + self.staked - self.paid
+        }
+
+        pub fn assert_invariant(&self) {
+            let vault_sum: u64 = self.vaults.iter().map(|v| v.balance).sum();
+            assert_eq!(vault_sum, self.table.total_vault_balance);
+            // The books split the house's money between what it can use and what a pool still owes
+            assert_eq!(self.house_available() + self.table.pool_balance, self.house_equity());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::harness::unbooked_bet;
+    use crate::BetKind;
+
+    #[test]
+    fn removed_bet_leaves_no_trace_on_the_books() {
+        let mut table = Table {
+            sides: 6,
+            dice_count: 1,
+            ..Default::default()
+        };
+        let mut roll_state = RollState::default();
+        let mut profile = PlayerProfile::default();
+        let roll = Pubkey::new_unique();
+
+        profile.next_bet(roll, 100_000, 500_000).unwrap();
+        let mut bet_state = BetState {
+            roll,
+            ..unbooked_bet(BetKind::Exact(2), 100_000)
+        };
+        book_bet(&mut table, &mut roll_state, &mut bet_state).unwrap();
+
+        let refunded = remove_bet(&mut table, &mut roll_state, &mut profile, &bet_state).unwrap();
+        assert_eq!(refunded, 100_000);
+        assert_eq!(table.outstanding_liabilities, 0);
+        assert_eq!(roll_state.total_bets_amount, 0);
+        assert_eq!(roll_state.total_payouts, 0);
+        assert_eq!(roll_state.worst_case_liability(), 0);
+        assert_eq!(roll_state.open_bets, 0);
+        assert_eq!(profile.roll_stake, 0);
+    }
+
+    #[test]
+    fn commission_is_locked_in_when_the_bet_is_placed() {
+        let mut table = Table {
+            sides: 6,
+            dice_count: 1,
+            ..Default::default()
+        };
+        table.config.commission_bps = 250;
+        let mut roll_state = RollState::default();
+        let mut bet_state = unbooked_bet(BetKind::Exact(4), 100_000);
+        book_bet(&mut table, &mut roll_state, &mut bet_state).unwrap();
+
+        // Raising the commission before the claim doesn't reach the open bet
+        table.config.commission_bps = 1_000;
+        settle_books(&mut table, &mut roll_state, [4, 0, 0]).unwrap();
+        assert_eq!(claim_bet(&mut table, &mut roll_state, &mut bet_state).unwrap(), 585_000);
+    }
+
+    #[test]
+    fn pool_rake_is_locked_in_when_the_round_opens() {
+        let mut table = Table {
+            sides: 6,
+            dice_count: 1,
+            mode: GameMode::PoolRollover,
+            ..Default::default()
+        };
+        table.config.commission_bps = 1_000;
+        let mut roll_state = RollState {
+            commission_bps: 500,
+            ..Default::default()
+        };
+        let mut bet_state = unbooked_bet(BetKind::Exact(1), 100_000);
+        book_bet(&mut table, &mut roll_state, &mut bet_state).unwrap();
+
+        settle_books(&mut table, &mut roll_state, [1, 0, 0]).unwrap();
+        assert_eq!(roll_state.pool_prize, 95_000);
+        assert_eq!(claim_bet(&mut table, &mut roll_state, &mut bet_state).unwrap(), 95_000);
+    }
+
+    #[test]
+    fn losing_bets_stop_holding_the_treasury_once_settled() {
+        let mut table = Table {
+            sides: 6,
+            dice_count: 1,
+            ..Default::default()
+        };
+        table.config.min_pot_lamports = 100_000;
+        let mut roll_state = RollState::default();
+        let mut winner = unbooked_bet(BetKind::Exact(5), 100_000);
+        let mut loser = unbooked_bet(BetKind::Low, 100_000);
+        book_bet(&mut table, &mut roll_state, &mut winner).unwrap();
+        book_bet(&mut table, &mut roll_state, &mut loser).unwrap();
+        assert_eq!(table.outstanding_liabilities, 600_000 + 200_000);
+
+        settle_books(&mut table, &mut roll_state, [5, 0, 0]).unwrap();
+        assert_eq!(table.outstanding_liabilities, 600_000);
+        assert_eq!(table.settled_liabilities, 600_000);
+        // Only the winner's payout is held back from a withdrawal
+        assert!(table.check_withdrawal(1_000_000, 300_000).is_ok());
+        let err = table.check_withdrawal(1_000_000, 300_001).unwrap_err();
+        assert_eq!(err, ErrorCode::InsufficientTreasuryForWithdrawal.into());
+
+        // The loser never claims, the winner's claim clears the rest
+        assert_eq!(claim_bet(&mut table, &mut roll_state, &mut winner).unwrap(), 600_000);
+        assert_eq!(table.outstanding_liabilities, 0);
+        assert_eq!(table.settled_liabilities, 0);
+        assert_eq!(claim_bet(&mut table, &mut roll_state, &mut loser).unwrap(), 0);
+        assert_eq!(table.outstanding_liabilities, 0);
+    }
+
+    #[test]
+    fn settled_roll_only_holds_its_winners_against_the_next_roll() {
+        let mut table = Table {
+            sides: 6,
+            dice_count: 1,
+            ..Default::default()
+        };
+        let mut settled = RollState::default();
+        for face in 1..=6 {
+            let mut bet_state = unbooked_bet(BetKind::Exact(face), 100_000);
+            book_bet(&mut table, &mut settled, &mut bet_state).unwrap();
+        }
+        settle_books(&mut table, &mut settled, [3, 0, 0]).unwrap();
+
+        // A bet on the next roll needs room for its own payout and the one unpaid winner,
+        // not for the five losers of the settled roll
+        let mut next = RollState::default();
+        let mut bet_state = unbooked_bet(BetKind::Exact(1), 100_000);
+        book_bet(&mut table, &mut next, &mut bet_state).unwrap();
+        assert_eq!(required_reserve(&table, &next).unwrap(), 600_000 + 600_000);
+    }
+}
@@ -1,193 +1,539 @@
 #![allow(deprecated)]
 
 use anchor_lang::prelude::*;
-// TODO: Update to use solana_system_interface crate when project dependencies are updated
-use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
+pub mod bankroll;
+pub mod dice;
 pub mod errors;
 pub mod events;
+pub mod instructions;
+pub mod jackpot;
+pub mod ledger;
+pub mod pause;
+pub mod pool;
+pub mod profile;
+pub mod session;
+pub mod treasury;
+pub mod vault;
 
+pub use instructions::*;
+pub use bankroll::LiquidityWithdrawal;
+pub use dice::BetKind;
+pub use jackpot::Jackpot;
+pub use pool::GameMode;
+pub use profile::PlayerProfile;
+pub use session::SessionToken;
+pub use vault::PlayerVault;
+
+use crate::dice::{MAX_DICE, MAX_DIE_SIDES};
 use crate::errors::ErrorCode;
-use crate::events::BetPlaced;
 
 declare_id!("FRb5eZnHH434Z5tQzoifEVL5MC8XCs4t3jXkkraszuZg");
 
-// Define constants for bet limits in lamports
-const MIN_BET_LAMPORTS: u64 = 1_000_000; // 0.001 SOL
-const MAX_BET_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
+// Commission is expressed in basis points of the gross payout
+pub const BPS_DENOMINATOR: u64 = 10_000;
 
 #[program]
 pub mod my_new_prog {
     use super::*;
 
-    pub fn initialize_contract(
-        ctx: Context<InitializeContract>,
-        _max_bet_config: u64,
+    pub fn create_table(
+        ctx: Context<CreateTable>,
+        table_id: u64,
+        sides: u8,
+        dice_count: u8,
+        mode: GameMode,
+        config: GameConfig,
     ) -> Result<()> {
-        let global_state = &mut ctx.accounts.global_state;
-        global_state.authority = ctx.accounts.authority.key();
-        global_state.treasury_pda = ctx.accounts.treasury_pda_account.key();
-        global_state.treasury_bump = ctx.bumps.treasury_pda_account;
-        global_state.bump = ctx.bumps.global_state;
-        Ok(())
-    }
+        dice::validate_dice(sides, dice_count)?;
+        // Pools split the stakes on the winning face, which needs a single die
+        require!(!mode.is_pool() || dice_count == 1, ErrorCode::InvalidGameMode);
+        config.validate()?;
 
-    pub fn place_bet(ctx: Context<PlaceBet>, guess: u8, amount: u64) -> Result<()> {
-        require!(guess >= 1 && guess <= 6, ErrorCode::InvalidGuess);
-        require!(amount >= MIN_BET_LAMPORTS, ErrorCode::BetTooSmall);
-        require!(amount <= MAX_BET_LAMPORTS, ErrorCode::BetTooLarge);
-    
-        // Ensure previous bet is claimed if it exists
-        if let Some(previous_bet_state_account) = &ctx.accounts.previous_bet_state {
-            let previous_bet = previous_bet_state_account;
-    
-            let previous_roll_state_account = match &ctx.accounts.previous_roll_state {
-                Some(acc) => acc,
-                None => return Err(ErrorCode::InvalidPreviousRollAccount.into()),
-            };
-    
-            require!(
-                previous_roll_state_account.key() == previous_bet.roll,
-                ErrorCode::InvalidPreviousRollAccount
-            );
-    
-            if previous_roll_state_account.revealed && !previous_bet.claimed {
-                return Err(ErrorCode::PreviousBetUnclaimed.into());
+        let table = &mut ctx.accounts.table;
+        table.creator = ctx.accounts.authority.key();
+        table.authority = ctx.accounts.authority.key();
+        table.pending_authority = Pubkey::default();
+        table.operator = Pubkey::default();
+        table.guardian = Pubkey::default();
+        table.paused = 0;
+        table.table_id = table_id;
+        table.sides = sides;
+        table.dice_count = dice_count;
+        table.mode = mode;
+        table.treasury_pda = ctx.accounts.treasury_pda_account.key();
+        table.treasury_bump = ctx.bumps.treasury_pda_account;
+        table.bump = ctx.bumps.table;
+
+        // A mint turns this into a token game, paid from a token account owned by the treasury PDA
+        match (&ctx.accounts.mint, &ctx.accounts.treasury_token_account) {
+            (Some(mint), Some(treasury_token_account)) => {
+                require_keys_eq!(treasury_token_account.mint, mint.key(), ErrorCode::InvalidMint);
+                require_keys_eq!(
+                    treasury_token_account.owner,
+                    ctx.accounts.treasury_pda_account.key(),
+                    ErrorCode::InvalidTokenAccount
+                );
+                table.mint = mint.key();
+                table.treasury_token_account = treasury_token_account.key();
+            }
+            (None, None) => {
+                table.mint = Pubkey::default();
+                table.treasury_token_account = Pubkey::default();
             }
+            _ => return err!(ErrorCode::MissingTokenAccounts),
         }
-    
-        // Set up new bet state
-        let bet_state = &mut ctx.accounts.bet_state;
-        bet_state.player = ctx.accounts.player.key();
-        bet_state.roll = ctx.accounts.roll_state.key();
-        bet_state.guess = guess;
-        bet_state.amount = amount;
-        bet_state.claimed = false;
-        bet_state.bump = ctx.bumps.bet_state;
-    
-        // Transfer lamports to treasury
-        anchor_lang::solana_program::program::invoke(
-            &system_instruction::transfer(
-                ctx.accounts.player.key,
-                ctx.accounts.treasury_pda_account.key,
-                amount,
-            ),
-            &[
-                ctx.accounts.player.to_account_info(),
-                ctx.accounts.treasury_pda_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
-    
-        // Update total bets on the roll
-        let roll_state = &mut ctx.accounts.roll_state;
-        roll_state.total_bets_amount = roll_state
-            .total_bets_amount
-            .checked_add(amount)
-            .ok_or(ErrorCode::MathOverflow)?;
-    
-        emit!(BetPlaced {
-            user: ctx.accounts.player.key(),
-            amount,
-        });
-    
+
+        table.config = config;
+        table.outstanding_liabilities = 0;
+        table.settled_liabilities = 0;
+        table.total_vault_balance = 0;
+        table.pool_balance = 0;
+        table.rollover_pool = 0;
+        table.queued_withdrawal = 0;
+        table.withdrawal_unlock_slot = 0;
+        table.jackpot = Pubkey::default();
+        table.jackpot_balance = 0;
+        table.share_mint = Pubkey::default();
+        table.share_cooldown_slots = 0;
+        table.committed_rolls = 0;
+        table.streak_outcome = 0;
+        table.streak_length = 0;
+        table.round_id = 0;
+        table.current_round = Pubkey::default();
         Ok(())
     }
-    
+
+    pub fn place_bet(ctx: Context<PlaceBet>, kind: BetKind, amount: u64) -> Result<()> {
+        instructions::place_bet::handler(ctx, kind, amount)
+    }
+
+    pub fn open_round(ctx: Context<OpenRound>) -> Result<()> {
+        instructions::open_round::handler(ctx)
+    }
+
+    pub fn trigger_random_roll(ctx: Context<TriggerRandomRoll>) -> Result<()> {
+        instructions::trigger_random_roll::handler(ctx)
+    }
+
+    pub fn settle_roll(ctx: Context<SettleRoll>) -> Result<()> {
+        instructions::settle_roll::handler(ctx)
+    }
+
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        instructions::claim_winnings::handler(ctx)
+    }
+
+    pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
+        instructions::cancel_bet::handler(ctx)
+    }
+
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+        instructions::queue_withdrawal::handler(ctx, amount)
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        instructions::execute_withdrawal::handler(ctx)
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, config: GameConfig) -> Result<()> {
+        instructions::update_config::handler(ctx, config)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        instructions::deposit::handler(ctx, amount)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        instructions::withdraw::handler(ctx, amount)
+    }
+
+    pub fn void_roll(ctx: Context<VoidRoll>) -> Result<()> {
+        instructions::void_roll::handler(ctx)
+    }
+
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        instructions::refund_bet::handler(ctx)
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expiry_slot: u64,
+        max_stake: u64,
+        allowed_instructions: u8,
+    ) -> Result<()> {
+        instructions::create_session::handler(ctx, session_key, expiry_slot, max_stake, allowed_instructions)
+    }
+
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
+    pub fn create_jackpot(
+        ctx: Context<CreateJackpot>,
+        contribution_bps: u16,
+        cap: u64,
+        streak_target: u8,
+        award_on_triple: bool,
+    ) -> Result<()> {
+        instructions::create_jackpot::handler(ctx, contribution_bps, cap, streak_target, award_on_triple)
+    }
+
+    pub fn claim_jackpot(ctx: Context<ClaimJackpot>) -> Result<()> {
+        instructions::claim_jackpot::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        instructions::set_operator::handler(ctx, operator)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
+        instructions::pause::handler(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+        instructions::unpause::handler(ctx, flags)
+    }
+
+    pub fn close_roll(ctx: Context<CloseRoll>) -> Result<()> {
+        instructions::close_roll::handler(ctx)
+    }
+
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        instructions::close_bet::handler(ctx)
+    }
+
+    pub fn create_share_mint(ctx: Context<CreateShareMint>, cooldown_slots: u64) -> Result<()> {
+        instructions::create_share_mint::handler(ctx, cooldown_slots)
+    }
+
+    pub fn provide_liquidity(ctx: Context<ProvideLiquidity>, amount: u64) -> Result<()> {
+        instructions::provide_liquidity::handler(ctx, amount)
+    }
+
+    pub fn request_liquidity_withdrawal(
+        ctx: Context<RequestLiquidityWithdrawal>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::request_liquidity_withdrawal::handler(ctx, shares)
+    }
+
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>) -> Result<()> {
+        instructions::withdraw_liquidity::handler(ctx)
+    }
 }
 
 #[derive(Accounts)]
-pub struct InitializeContract<'info> {
+#[instruction(table_id: u64)]
+pub struct CreateTable<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 1 + 1,
-        seeds = [b"global-state"],
+        space = 8 + std::mem::size_of::<Table>(), // 8 bytes for discriminator
+        seeds = [b"table", authority.key().as_ref(), table_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub global_state: Account<'info, GlobalState>,
+    pub table: Account<'info, Table>,
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
         init,
         payer = authority,
         space = 8,
-        seeds = [b"treasury", authority.key().as_ref()],
+        seeds = [b"treasury", table.key().as_ref()],
         bump
     )]
     /// CHECK: This is the treasury PDA, its existence and ownership is checked by Anchor.
     pub treasury_pda_account: Account<'info, TreasuryAccount>,
     pub system_program: Program<'info, System>,
+
+    // Only passed for token games, leave both out for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 }
 
-#[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+// Account definitions
+#[account]
+#[derive(Default)]
+pub struct Table {
+    pub creator: Pubkey,           // Authority the table was created by, part of its seeds and never changed
+    pub authority: Pubkey,         // Withdraws from the treasury and changes config
+    pub pending_authority: Pubkey, // Proposed new authority until it accepts, Pubkey::default() if none
+    pub operator: Pubkey,          // May also trigger and settle rolls, Pubkey::default() if none
+    pub guardian: Pubkey,          // May pause but not unpause, Pubkey::default() if none
+    pub paused: u8,                // Bitmask of pause::PAUSE_* flags
+    pub table_id: u64,          // Lets one operator run several tables, e.g. one per stake tier
+    pub sides: u8,              // Faces on this table's die, fixed at creation
+    pub dice_count: u8,         // Dice thrown per roll, 2 or 3 for craps and Sic Bo tables
+    pub mode: GameMode,         // Fixed odds paid by the house, or pari-mutuel pools
+    pub treasury_pda: Pubkey,
+    pub treasury_bump: u8,
+    pub bump: u8,
+    pub mint: Pubkey,                   // Pubkey::default() for native SOL games
+    pub treasury_token_account: Pubkey, // Token account owned by the treasury PDA, token games only
+    pub config: GameConfig,
+    pub outstanding_liabilities: u64, // Gross payouts reserved for open bets and owed to unclaimed winners
+    pub settled_liabilities: u64, // Part of `outstanding_liabilities` owed to winners of settled rolls
+    pub total_vault_balance: u64, // Player funds held in vaults, not available to the house
+    pub pool_balance: u64,  // Pool stakes not yet paid out, including any rollover
+    pub rollover_pool: u64, // Pot nobody won, added to the next triggered roll
+    pub queued_withdrawal: u64,      // Treasury withdrawal announced by the authority, zero if none
+    pub withdrawal_unlock_slot: u64, // First slot the queued withdrawal can run in
+    pub jackpot: Pubkey,      // Jackpot PDA, Pubkey::default() until one is created
+    pub jackpot_balance: u64, // Jackpot pot plus awards not yet claimed
+    pub streak_outcome: u8,   // Outcome slot of the most recent settled roll
+    pub streak_length: u8,    // Rolls in a row that came up with `streak_outcome`
+    pub share_mint: Pubkey,         // Liquidity provider shares, Pubkey::default() until created
+    pub share_cooldown_slots: u64,  // Slots between requesting a liquidity withdrawal and running it
+    pub committed_rolls: u32,       // Rolls with randomness committed that are not yet settled or voided
+    pub round_id: u64,          // Id the next triggered roll gets, increases with each trigger
+    pub current_round: Pubkey,  // RollState of the most recently triggered round
+}
 
-    #[account(mut, seeds = [b"global-state"], bump = global_state.bump)]
-    pub global_state: Account<'info, GlobalState>,
+impl Table {
+    /// Whether `key` may trigger and settle rolls.
+    pub fn can_operate(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.operator != Pubkey::default() && *key == self.operator)
+    }
 
-    #[account(mut, seeds = [b"roll", roll_state.randomness_account.as_ref()], bump = roll_state.bump)]
-    pub roll_state: Account<'info, RollState>,
+    /// Whether `key` may pause instructions on this table.
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.guardian != Pubkey::default() && *key == self.guardian)
+    }
 
-    #[account(
-        init,
-        payer = player,
-        space = 8 + std::mem::size_of::<BetState>(), // 8 bytes for discriminator
-        seeds = [b"bet", roll_state.key().as_ref(), player.key().as_ref()],
-        bump
-    )]
-    pub bet_state: Account<'info, BetState>,
+    pub fn is_native(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
 
-    #[account(
-        mut,
-        seeds = [b"treasury", global_state.authority.key().as_ref()],
-        bump = global_state.treasury_bump
-    )]
-    /// CHECK: Treasury is just a SOL holding account
-    pub treasury_pda_account: AccountInfo<'info>,
+    /// Checks `amount` can leave the treasury and still cover the minimum pot and every
+    /// open bet. Fails once liquidity providers share the bankroll, they redeem shares instead.
+    pub fn check_withdrawal(&self, treasury_available: u64, amount: u64) -> Result<()> {
+        require!(
+            self.share_mint == Pubkey::default(),
+            ErrorCode::TreasuryHasShareholders
+        );
+        require!(
+            self.house_available(treasury_available) >= self.withdrawal_floor(amount)?,
+            ErrorCode::InsufficientTreasuryForWithdrawal
+        );
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
+    /// House funds that must be on hand before `amount` leaves the bankroll: the minimum
+    /// pot plus every open reserve and unclaimed win.
+    pub fn withdrawal_floor(&self, amount: u64) -> Result<u64> {
+        self.config
+            .min_pot_lamports
+            .checked_add(self.outstanding_liabilities)
+            .and_then(|floor| floor.checked_add(amount))
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
 
-    #[account(
-        owner = crate::ID,
-        has_one = player @ ErrorCode::PreviousBetDoesNotBelongToPlayer
-    )]
-    pub previous_bet_state: Option<Account<'info, BetState>>,
+    /// Part of the treasury balance that belongs to the house rather than to player
+    /// vaults, pool players or the jackpot.
+    pub fn house_available(&self, treasury_available: u64) -> u64 {
+        treasury_available
+            .saturating_sub(self.total_vault_balance)
+            .saturating_sub(self.pool_balance)
+            .saturating_sub(self.jackpot_balance)
+    }
+}
 
-    pub previous_roll_state: Option<Account<'info, RollState>>,
+// Amounts are in lamports for native SOL games and in base units of the mint for token games
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GameConfig {
+    pub min_bet_lamports: u64,
+    pub max_bet_lamports: u64,
+    pub max_roll_stake_lamports: u64, // Total one player may stake on a single roll across all their bets
+    pub commission_bps: u16,    // House commission taken from the gross payout
+    pub min_pot_lamports: u64,  // Floor the treasury must keep above rent to run rolls
+    pub reveal_timeout_slots: u64, // Slots after a trigger before an unrevealed roll can be voided
+    pub withdrawal_delay_slots: u64, // Slots between queueing a treasury withdrawal and running it
 }
 
-// Account definitions
-#[account]
-pub struct GlobalState {
-    pub authority: Pubkey,
-    pub treasury_pda: Pubkey,
-    pub treasury_bump: u8,
-    pub bump: u8,
+impl GameConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min_bet_lamports > 0, ErrorCode::InvalidBetLimits);
+        require!(
+            self.min_bet_lamports <= self.max_bet_lamports,
+            ErrorCode::InvalidBetLimits
+        );
+        require!(
+            self.max_bet_lamports <= self.max_roll_stake_lamports,
+            ErrorCode::InvalidBetLimits
+        );
+        require!(
+            self.commission_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidCommissionBps
+        );
+        require!(self.reveal_timeout_slots > 0, ErrorCode::InvalidRevealTimeout);
+        Ok(())
+    }
 }
 
 #[account]
 pub struct RollState {
+    pub table: Pubkey,
+    pub round_id: u64,
     pub randomness_account: Pubkey,
     pub revealed: bool,
-    pub result: Option<u8>,
+    pub result: Option<[u8; MAX_DICE]>, // One face per die thrown, unused dice are zero
     pub total_bets_amount: u64,
+    // Gross payouts owed if each outcome comes up: per face for a single die,
+    // per sorted throw for multi-dice tables
+    pub face_liabilities: [u64; MAX_DIE_SIDES as usize],
+    pub total_payouts: u64, // Gross payouts of every open bet, a multi-face bet counts once
+    pub face_stakes: [u64; MAX_DIE_SIDES as usize], // Stakes backing each outcome, the split basis for pools and jackpots
+    pub carried_pool: u64, // Pool tables only, pot rolled over from earlier rolls
+    pub pool_prize: u64,   // Pool tables only, pot less rake split among the winning face
+    pub pool_unclaimed: u64, // Pool tables only, prize or refunds not yet paid, the dust goes to the house on close
+    pub commission_bps: u16, // Pool tables only, rake taken at settle, locked in when the round opens
+    pub jackpot_award: u64, // Jackpot won by this roll, split among its winning bets
+    pub jackpot_unclaimed: u64, // Part of the award not yet claimed, the dust goes to the house on close
+    pub commit_slot: u64, // Switchboard seed slot the roll was triggered with, zero while betting is open
+    pub expiry_slot: u64, // After this slot an unrevealed roll can be voided
+    pub last_bet_slot: u64, // Slot of the latest bet, the commit's seed must come after it
+    pub voided: bool,     // True once the roll timed out and its bets are refundable
+    pub created_slot: u64,
+    pub created_at: i64,
+    pub settled_slot: u64, // Zero until the roll is settled
+    pub settled_at: i64,   // Zero until the roll is settled
+    pub payer: Pubkey,     // Operator who paid the roll's rent, refunded by close_roll
+    pub open_bets: u32,    // Bet accounts on this roll not yet closed
     pub bump: u8,
 }
 
+// Arrays this long don't derive Default
+impl Default for RollState {
+    fn default() -> Self {
+        Self {
+            table: Pubkey::default(),
+            round_id: 0,
+            randomness_account: Pubkey::default(),
+            revealed: false,
+            result: None,
+            total_bets_amount: 0,
+            face_liabilities: [0; MAX_DIE_SIDES as usize],
+            total_payouts: 0,
+            face_stakes: [0; MAX_DIE_SIDES as usize],
+            carried_pool: 0,
+            pool_prize: 0,
+            pool_unclaimed: 0,
+            commission_bps: 0,
+            jackpot_award: 0,
+            jackpot_unclaimed: 0,
+            commit_slot: 0,
+            expiry_slot: 0,
+            last_bet_slot: 0,
+            voided: false,
+            created_slot: 0,
+            created_at: 0,
+            settled_slot: 0,
+            settled_at: 0,
+            payer: Pubkey::default(),
+            open_bets: 0,
+            bump: 0,
+        }
+    }
+}
+
+impl RollState {
+    /// True once trigger_random_roll has bound a randomness account, which closes betting.
+    pub fn is_committed(&self) -> bool {
+        self.randomness_account != Pubkey::default()
+    }
+
+    /// True once the oracle has revealed a value for the seed this roll was committed to.
+    /// A re-commit moves `seed_slot` on, and the value no longer belongs to this roll.
+    pub fn reveal_landed(&self, seed_slot: u64, reveal_slot: u64) -> bool {
+        self.is_committed() && seed_slot == self.commit_slot && reveal_slot > self.commit_slot
+    }
+
+    /// Counts one of this roll's bet accounts as closed.
+    pub fn close_bet(&mut self) -> Result<()> {
+        self.open_bets = self.open_bets.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?;
+        Ok(())
+    }
+
+    /// Books a bet's gross payout and stake against every face in its winning `mask`.
+    pub fn add_liability(&mut self, mask: u128, payout: u64, stake: u64) -> Result<()> {
+        for face in 0..MAX_DIE_SIDES as usize {
+            if mask & (1 << face) != 0 {
+                self.face_liabilities[face] = self.face_liabilities[face]
+                    .checked_add(payout)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                self.face_stakes[face] = self.face_stakes[face]
+                    .checked_add(stake)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+        }
+        self.total_payouts = self.total_payouts.checked_add(payout).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Releases what `add_liability` booked, for a bet that is cancelled or refunded.
+    pub fn remove_liability(&mut self, mask: u128, payout: u64, stake: u64) -> Result<()> {
+        for face in 0..MAX_DIE_SIDES as usize {
+            if mask & (1 << face) != 0 {
+                self.face_liabilities[face] = self.face_liabilities[face]
+                    .checked_sub(payout)
+                    .ok_or(ErrorCode::MathUnderflow)?;
+                self.face_stakes[face] = self.face_stakes[face]
+                    .checked_sub(stake)
+                    .ok_or(ErrorCode::MathUnderflow)?;
+            }
+        }
+        self.total_payouts = self.total_payouts.checked_sub(payout).ok_or(ErrorCode::MathUnderflow)?;
+        Ok(())
+    }
+
+    /// What the treasury pays out if the most heavily backed face wins.
+    pub fn worst_case_liability(&self) -> u64 {
+        self.face_liabilities.iter().copied().max().unwrap_or(0)
+    }
+}
+
 #[account]
 pub struct BetState {
     pub player: Pubkey,       // 32 bytes
     pub roll: Pubkey,         // 32 bytes
-    pub guess: u8,            // 1 byte
+    pub kind: BetKind,        // Up to 17 bytes
     pub amount: u64,          // 8 bytes
     pub claimed: bool,        // 1 byte
+    pub payout: u64,          // 8 bytes - Gross payout if the bet wins, locked at bet time
+    pub commission: u64,      // 8 bytes - Commission taken from the payout on a win, locked at bet time
+    pub jackpot_claimed: bool, // 1 byte
+    pub nonce: u64,           // 8 bytes - The player's bet nonce, part of the seeds
     pub bump: u8,             // 1 byte (If you intend to store the bump in the account itself)
 }
 
 #[account]
 pub struct TreasuryAccount {
     // This struct holds no custom data, just SOL. Anchor adds an 8-byte discriminator.
-}
\ No newline at end of file
+}
+
+impl TreasuryAccount {
+    /// Lamports the treasury can pay out without dropping below rent exemption.
+    pub fn available_lamports(treasury: &AccountInfo) -> Result<u64> {
+        let rent_exempt_reserve = Rent::get()?.minimum_balance(treasury.data_len());
+        Ok(treasury.lamports().saturating_sub(rent_exempt_reserve))
+    }
+}
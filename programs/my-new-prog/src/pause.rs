@@ -0,0 +1,54 @@
+// programs/sb_randomness/src/pause.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::errors::ErrorCode;
+
+// Bits of `Table::paused`. Player funds are never paused: cancelling, refunding and
+// withdrawing from a vault always stay open, and claims can be paused separately from bets.
+pub const PAUSE_PLACE_BET: u8 = 1 << 0;
+pub const PAUSE_TRIGGER_ROLL: u8 = 1 << 1;
+pub const PAUSE_SETTLE: u8 = 1 << 2;
+pub const PAUSE_CLAIM: u8 = 1 << 3;
+pub const PAUSE_WITHDRAW: u8 = 1 << 4; // Treasury and liquidity provider withdrawals
+pub const PAUSE_ALL: u8 =
+    PAUSE_PLACE_BET | PAUSE_TRIGGER_ROLL | PAUSE_SETTLE | PAUSE_CLAIM | PAUSE_WITHDRAW;
+
+/// Fails with `ProgramPaused` while any of `flags` is paused on the table.
+pub fn require_not_paused(table: &Table, flags: u8) -> Result<()> {
+    require!(table.paused & flags == 0, ErrorCode::ProgramPaused);
+    Ok(())
+}
+
+/// Flags passed to pause or unpause must name at least one known instruction.
+pub fn validate_flags(flags: u8) -> Result<()> {
+    require!(flags != 0 && flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pausing_bets_leaves_claims_open() {
+        let table = Table {
+            paused: PAUSE_PLACE_BET | PAUSE_TRIGGER_ROLL,
+            ..Default::default()
+        };
+
+        let err = require_not_paused(&table, PAUSE_PLACE_BET).unwrap_err();
+        assert_eq!(err, ErrorCode::ProgramPaused.into());
+        require_not_paused(&table, PAUSE_SETTLE).unwrap();
+        require_not_paused(&table, PAUSE_CLAIM).unwrap();
+    }
+
+    #[test]
+    fn pause_flags_must_be_known() {
+        for flags in [0, 1 << 5, PAUSE_CLAIM | 1 << 7] {
+            assert_eq!(validate_flags(flags).unwrap_err(), ErrorCode::InvalidPauseFlags.into());
+        }
+        validate_flags(PAUSE_ALL).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+// programs/sb_randomness/src/pool.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{ledger, BetKind, RollState, Table};
+use crate::errors::ErrorCode;
+
+/// How a table pays its winners.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GameMode {
+    #[default]
+    FixedOdds,    // The house pays posted odds from the treasury
+    PoolRollover, // Winners split the roll's stakes, a pool nobody wins carries to the next roll
+    PoolRefund,   // Winners split the roll's stakes, a pool nobody wins is refunded
+}
+
+impl GameMode {
+    pub fn is_pool(&self) -> bool {
+        *self != GameMode::FixedOdds
+    }
+}
+
+/// How a settled pool is paid out.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PoolOutcome {
+    Winners { prize: u64, rake: u64 }, // `prize` is split pro rata among the winning face
+    Rollover(u64),                     // Nobody won, the whole pot moves to the next roll
+    Refund,                            // Nobody won, every bet gets its stake back
+}
+
+/// Slot in `RollState::face_stakes` a bet stakes into. Pools only take bets on a single face.
+pub fn pool_face(kind: &BetKind, sides: u8) -> Result<usize> {
+    match *kind {
+        BetKind::Exact(face) if (1..=sides).contains(&face) => Ok((face - 1) as usize),
+        _ => err!(ErrorCode::InvalidPoolBet),
+    }
+}
+
+/// Books a stake into the roll's pool. It stays owed to the pool's players until paid out.
+pub fn add_stake(table: &mut Table, roll_state: &mut RollState, face: usize, stake: u64) -> Result<()> {
+    roll_state.face_stakes[face] = roll_state.face_stakes[face]
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    table.pool_balance = table.pool_balance.checked_add(stake).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Takes a cancelled or refunded stake back out of the roll's pool.
+pub fn remove_stake(table: &mut Table, roll_state: &mut RollState, face: usize, stake: u64) -> Result<()> {
+    roll_state.face_stakes[face] = roll_state.face_stakes[face]
+        .checked_sub(stake)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    release(table, stake)
+}
+
+/// Pays `amount` out of the pool funds held in the treasury.
+pub fn release(table: &mut Table, amount: u64) -> Result<()> {
+    table.pool_balance = table.pool_balance.checked_sub(amount).ok_or(ErrorCode::MathUnderflow)?;
+    Ok(())
+}
+
+/// Decides how a pot of `pot` is paid once the winning face is known.
+/// The rake is only taken when somebody wins.
+pub fn settle_pool(mode: GameMode, pot: u64, winning_pool: u64, commission_bps: u16) -> Result<PoolOutcome> {
+    if winning_pool == 0 {
+        return Ok(match mode {
+            GameMode::PoolRefund => PoolOutcome::Refund,
+            _ => PoolOutcome::Rollover(pot),
+        });
+    }
+
+    let rake = ledger::commission_on(pot, commission_bps)?;
+    Ok(PoolOutcome::Winners {
+        prize: pot.checked_sub(rake).ok_or(ErrorCode::MathUnderflow)?,
+        rake,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::harness::Game;
+
+    const HOUSE_FUNDS: u64 = 1_000_000;
+
+    fn pool(mode: GameMode, commission_bps: u16) -> Game {
+        Game::new(mode, commission_bps, 1, HOUSE_FUNDS)
+    }
+
+    #[test]
+    fn winners_split_the_pot_less_rake_pro_rata() {
+        let mut pool = pool(GameMode::PoolRollover, 500);
+        pool.bet(0, 3, 100_000).unwrap();
+        pool.bet(0, 3, 300_000).unwrap();
+        pool.bet(0, 5, 600_000).unwrap();
+        pool.assert_invariant();
+
+        // 1_000_000 pot, 5% rake leaves 950_000 for the 400_000 staked on 3
+        let paid = pool.settle_and_claim(3);
+        assert_eq!(paid, vec![237_500, 712_500, 0]);
+        assert_eq!(pool.table.pool_balance, 0);
+        assert_eq!(pool.house_available(), HOUSE_FUNDS + 50_000);
+        pool.assert_invariant();
+    }
+
+    #[test]
+    fn rounding_dust_goes_to_the_house_on_close() {
+        let mut pool = pool(GameMode::PoolRollover, 0);
+        pool.bet(0, 1, 1).unwrap();
+        pool.bet(0, 1, 1).unwrap();
+        pool.bet(0, 1, 1).unwrap();
+        pool.bet(0, 2, 8).unwrap();
+
+        let paid = pool.settle_and_claim(1);
+        assert_eq!(paid, vec![3, 3, 3, 0]);
+        assert_eq!(pool.table.pool_balance, 2);
+        pool.assert_invariant();
+
+        // Closing the roll hands the dust to the house
+        assert_eq!(pool.close_roll(), 2);
+        assert_eq!(pool.table.pool_balance, 0);
+        assert_eq!(pool.house_available(), HOUSE_FUNDS + 2);
+        pool.assert_invariant();
+    }
+
+    #[test]
+    fn pool_nobody_wins_rolls_into_the_next_round() {
+        let mut pool = pool(GameMode::PoolRollover, 1_000);
+        pool.bet(0, 1, 200_000).unwrap();
+        pool.bet(0, 2, 300_000).unwrap();
+
+        let paid = pool.settle_and_claim(6);
+        assert_eq!(paid, vec![0, 0]);
+        assert_eq!(pool.table.rollover_pool, 500_000);
+        assert_eq!(pool.house_available(), HOUSE_FUNDS);
+        pool.assert_invariant();
+
+        // The carried pot is added to the next roll's, and raked only once someone wins
+        pool.next_roll();
+        pool.bet(0, 4, 500_000).unwrap();
+        let paid = pool.settle_and_claim(4);
+        assert_eq!(paid, vec![900_000]);
+        assert_eq!(pool.house_available(), HOUSE_FUNDS + 100_000);
+        assert_eq!(pool.table.pool_balance, 0);
+        pool.assert_invariant();
+    }
+
+    #[test]
+    fn pool_nobody_wins_is_refunded_without_rake() {
+        let mut pool = pool(GameMode::PoolRefund, 1_000);
+        pool.bet(0, 1, 200_000).unwrap();
+        pool.bet(0, 2, 300_000).unwrap();
+
+        let paid = pool.settle_and_claim(6);
+        assert_eq!(paid, vec![200_000, 300_000]);
+        assert_eq!(pool.table.rollover_pool, 0);
+        assert_eq!(pool.table.pool_balance, 0);
+        assert_eq!(pool.house_available(), HOUSE_FUNDS);
+        pool.assert_invariant();
+    }
+
+    #[test]
+    fn pools_only_take_single_face_bets() {
+        for kind in [BetKind::High, BetKind::Faces(0b11), BetKind::Exact(7), BetKind::Sum(7)] {
+            assert_eq!(pool_face(&kind, 6).unwrap_err(), ErrorCode::InvalidPoolBet.into());
+        }
+        assert_eq!(pool_face(&BetKind::Exact(6), 6).unwrap(), 5);
+    }
+}
@@ -0,0 +1,80 @@
+// programs/sb_randomness/src/profile.rs
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// A player's betting state on one table.
+///
+/// Each bet takes the next `bet_nonce` into its seeds, so a player can hold several
+/// bets on the same roll. The stake on the current roll is tracked against the table's cap.
+#[account]
+#[derive(Default)]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub table: Pubkey,
+    pub bet_nonce: u64,  // Seed of the player's next bet account
+    pub roll: Pubkey,    // Roll `roll_stake` was placed on
+    pub roll_stake: u64, // Stake the player has open on `roll`
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    /// Books `stake` on `roll` against `cap` and hands out the nonce for the new bet.
+    pub fn next_bet(&mut self, roll: Pubkey, stake: u64, cap: u64) -> Result<u64> {
+        if self.roll != roll {
+            self.roll = roll;
+            self.roll_stake = 0;
+        }
+        let roll_stake = self.roll_stake.checked_add(stake).ok_or(ErrorCode::MathOverflow)?;
+        require!(roll_stake <= cap, ErrorCode::RollStakeLimitExceeded);
+        self.roll_stake = roll_stake;
+
+        let nonce = self.bet_nonce;
+        self.bet_nonce = nonce.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(nonce)
+    }
+
+    /// Frees a cancelled bet's stake from the cap, if it was placed on the current roll.
+    pub fn release_stake(&mut self, roll: Pubkey, stake: u64) {
+        if self.roll == roll {
+            self.roll_stake = self.roll_stake.saturating_sub(stake);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cap_covers_every_bet_on_the_roll() {
+        let mut profile = PlayerProfile::default();
+        let roll = Pubkey::new_unique();
+
+        assert_eq!(profile.next_bet(roll, 300, 500).unwrap(), 0);
+        assert_eq!(profile.next_bet(roll, 200, 500).unwrap(), 1);
+
+        let err = profile.next_bet(roll, 1, 500).unwrap_err();
+        assert_eq!(err, ErrorCode::RollStakeLimitExceeded.into());
+        assert_eq!(profile.bet_nonce, 2);
+
+        // Cancelling frees room under the cap again
+        profile.release_stake(roll, 200);
+        assert_eq!(profile.next_bet(roll, 100, 500).unwrap(), 2);
+    }
+
+    #[test]
+    fn cap_starts_over_on_a_new_roll() {
+        let mut profile = PlayerProfile::default();
+        profile.next_bet(Pubkey::new_unique(), 500, 500).unwrap();
+
+        let next_roll = Pubkey::new_unique();
+        assert_eq!(profile.next_bet(next_roll, 500, 500).unwrap(), 1);
+        assert_eq!(profile.roll_stake, 500);
+
+        // A late cancel on the old roll leaves the new roll's stake alone
+        profile.release_stake(Pubkey::new_unique(), 500);
+        assert_eq!(profile.roll_stake, 500);
+    }
+}
@@ -0,0 +1,120 @@
+// programs/sb_randomness/src/session.rs
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Bits of `SessionToken::allowed_instructions`
+pub const SESSION_PLACE_BET: u8 = 1 << 0;
+pub const SESSION_CLAIM_WINNINGS: u8 = 1 << 1;
+pub const SESSION_ALL_INSTRUCTIONS: u8 = SESSION_PLACE_BET | SESSION_CLAIM_WINNINGS;
+
+/// An ephemeral key the player has allowed to bet and claim for them on one table.
+///
+/// A session key can't spend from the player's wallet, so its bets are always
+/// staked from the player's vault and its claims pay out to the player.
+#[account]
+#[derive(Default)]
+pub struct SessionToken {
+    pub player: Pubkey,
+    pub table: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry_slot: u64, // Last slot the session key can act in
+    pub max_stake: u64,   // Total the session key may stake over its lifetime
+    pub staked: u64,      // Stake placed through this session so far
+    pub allowed_instructions: u8, // Bitmask of SESSION_* flags
+    pub bump: u8,
+}
+
+impl SessionToken {
+    /// Checks the session may run `instruction` in `slot` and books `stake` against its cap.
+    pub fn authorize(&mut self, slot: u64, instruction: u8, stake: u64) -> Result<()> {
+        require!(slot <= self.expiry_slot, ErrorCode::SessionExpired);
+        require!(
+            self.allowed_instructions & instruction == instruction,
+            ErrorCode::SessionInstructionNotAllowed
+        );
+
+        let staked = self.staked.checked_add(stake).ok_or(ErrorCode::MathOverflow)?;
+        require!(staked <= self.max_stake, ErrorCode::SessionStakeLimitExceeded);
+        self.staked = staked;
+        Ok(())
+    }
+}
+
+/// Lets `signer` act for `player`, either as the player themselves or through a session.
+/// The session account is already bound to both keys by its seeds.
+pub fn authorize_signer(
+    signer: &Pubkey,
+    player: &Pubkey,
+    session_token: Option<&mut SessionToken>,
+    instruction: u8,
+    stake: u64,
+) -> Result<()> {
+    if signer == player {
+        return Ok(());
+    }
+    let Some(session_token) = session_token else {
+        return err!(ErrorCode::InvalidSessionSigner);
+    };
+    session_token.authorize(Clock::get()?.slot, instruction, stake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(allowed_instructions: u8) -> SessionToken {
+        SessionToken {
+            expiry_slot: 1_000,
+            max_stake: 500,
+            allowed_instructions,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stake_cap_covers_the_whole_session() {
+        let mut session = session(SESSION_ALL_INSTRUCTIONS);
+        session.authorize(10, SESSION_PLACE_BET, 300).unwrap();
+        session.authorize(20, SESSION_PLACE_BET, 200).unwrap();
+
+        let err = session.authorize(30, SESSION_PLACE_BET, 1).unwrap_err();
+        assert_eq!(err, ErrorCode::SessionStakeLimitExceeded.into());
+        assert_eq!(session.staked, 500);
+
+        // Claims stake nothing, so they still go through once the cap is used up
+        session.authorize(40, SESSION_CLAIM_WINNINGS, 0).unwrap();
+    }
+
+    #[test]
+    fn expired_session_is_rejected() {
+        let mut session = session(SESSION_ALL_INSTRUCTIONS);
+        session.authorize(1_000, SESSION_CLAIM_WINNINGS, 0).unwrap();
+
+        let err = session.authorize(1_001, SESSION_CLAIM_WINNINGS, 0).unwrap_err();
+        assert_eq!(err, ErrorCode::SessionExpired.into());
+    }
+
+    #[test]
+    fn session_only_runs_allowed_instructions() {
+        let mut session = session(SESSION_CLAIM_WINNINGS);
+
+        let err = session.authorize(10, SESSION_PLACE_BET, 100).unwrap_err();
+        assert_eq!(err, ErrorCode::SessionInstructionNotAllowed.into());
+        assert_eq!(session.staked, 0);
+    }
+
+    #[test]
+    fn other_signer_needs_a_session() {
+        let err = authorize_signer(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            None,
+            SESSION_PLACE_BET,
+            100,
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidSessionSigner.into());
+    }
+}
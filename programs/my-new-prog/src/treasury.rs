@@ -0,0 +1,192 @@
+// programs/sb_randomness/src/treasury.rs
+
+use anchor_lang::prelude::*;
+// TODO: Update to use solana_system_interface crate when project dependencies are updated
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{PlayerVault, Table, TreasuryAccount};
+use crate::vault;
+use crate::errors::ErrorCode;
+
+/// Token accounts an instruction passes when the game is played with an SPL or Token-2022 mint.
+struct TokenAccounts<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    user_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+}
+
+/// Moves the game's currency in and out of the treasury PDA.
+///
+/// Native SOL games move lamports directly, token games use `transfer_checked`
+/// against the token account owned by the treasury PDA.
+pub struct Treasury<'a, 'info> {
+    pda: AccountInfo<'info>,
+    table: Pubkey,
+    bump: u8,
+    token: Option<TokenAccounts<'a, 'info>>,
+}
+
+impl<'a, 'info> Treasury<'a, 'info> {
+    /// Validates the optional token accounts against the mint configured on the table.
+    /// `user` is the wallet whose token account sends or receives funds.
+    pub fn new(
+        table: &Account<'info, Table>,
+        treasury_pda_account: &Account<'info, TreasuryAccount>,
+        user: &Pubkey,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        treasury_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        user_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let token = if table.is_native() {
+            None
+        } else {
+            let (Some(mint), Some(treasury_token_account)) = (mint, treasury_token_account) else {
+                return err!(ErrorCode::MissingTokenAccounts);
+            };
+            require_keys_eq!(mint.key(), table.mint, ErrorCode::InvalidMint);
+            require_keys_eq!(
+                treasury_token_account.key(),
+                table.treasury_token_account,
+                ErrorCode::InvalidTokenAccount
+            );
+
+            if let Some(user_token_account) = user_token_account {
+                require_keys_eq!(user_token_account.mint, mint.key(), ErrorCode::InvalidMint);
+                require_keys_eq!(user_token_account.owner, *user, ErrorCode::InvalidTokenAccount);
+            }
+            if let Some(token_program) = token_program {
+                require_keys_eq!(
+                    *mint.to_account_info().owner,
+                    token_program.key(),
+                    ErrorCode::InvalidMint
+                );
+            }
+
+            Some(TokenAccounts {
+                mint,
+                treasury_token_account,
+                user_token_account: user_token_account.as_ref(),
+                token_program: token_program.as_ref(),
+            })
+        };
+
+        Ok(Self {
+            pda: treasury_pda_account.to_account_info(),
+            table: table.key(),
+            bump: table.treasury_bump,
+            token,
+        })
+    }
+
+    /// Balance the treasury can pay out, read fresh so it reflects earlier transfers.
+    pub fn available(&self) -> Result<u64> {
+        match &self.token {
+            None => TreasuryAccount::available_lamports(&self.pda),
+            Some(token) => {
+                let info = token.treasury_token_account.to_account_info();
+                let data = info.try_borrow_data()?;
+                Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+            }
+        }
+    }
+
+    /// Pulls `amount` from the signing user into the treasury and returns what actually
+    /// arrived, which is less than `amount` for Token-2022 mints with a transfer fee.
+    pub fn deposit(
+        &self,
+        from: &Signer<'info>,
+        system_program: &Program<'info, System>,
+        amount: u64,
+    ) -> Result<u64> {
+        let Some(token) = &self.token else {
+            anchor_lang::solana_program::program::invoke(
+                &system_instruction::transfer(from.key, self.pda.key, amount),
+                &[
+                    from.to_account_info(),
+                    self.pda.clone(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+            return Ok(amount);
+        };
+
+        let (Some(user_token_account), Some(token_program)) =
+            (token.user_token_account, token.token_program)
+        else {
+            return err!(ErrorCode::MissingTokenAccounts);
+        };
+
+        let before = self.available()?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: user_token_account.to_account_info(),
+                    mint: token.mint.to_account_info(),
+                    to: token.treasury_token_account.to_account_info(),
+                    authority: from.to_account_info(),
+                },
+            ),
+            amount,
+            token.mint.decimals,
+        )?;
+        let after = self.available()?;
+
+        Ok(after.checked_sub(before).ok_or(ErrorCode::MathUnderflow)?)
+    }
+
+    /// Sends `amount` from the treasury to `to`, or to the user's token account for token games.
+    pub fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let Some(token) = &self.token else {
+            // The treasury is owned by this program, so lamports are moved directly
+            self.pda.sub_lamports(amount)?;
+            to.add_lamports(amount)?;
+            return Ok(());
+        };
+
+        let (Some(user_token_account), Some(token_program)) =
+            (token.user_token_account, token.token_program)
+        else {
+            return err!(ErrorCode::MissingTokenAccounts);
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", self.table.as_ref(), &[self.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: token.treasury_token_account.to_account_info(),
+                    mint: token.mint.to_account_info(),
+                    to: user_token_account.to_account_info(),
+                    authority: self.pda.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            token.mint.decimals,
+        )
+    }
+
+    /// Pays the player out of the house's funds: into their vault when one is passed,
+    /// otherwise to their wallet.
+    pub fn pay_player(
+        &self,
+        table: &mut Table,
+        player_vault: Option<&mut PlayerVault>,
+        player: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            table.house_available(self.available()?) >= amount,
+            ErrorCode::InsufficientTreasury
+        );
+        let to_wallet = vault::credit_payout(player_vault, table, amount)?;
+        if to_wallet > 0 {
+            self.pay(player, to_wallet)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+// programs/sb_randomness/src/vault.rs
+
+use anchor_lang::prelude::*;
+
+use crate::Table;
+use crate::errors::ErrorCode;
+
+/// A player's balance held inside the table's treasury.
+///
+/// The funds never leave the treasury while they sit in a vault, so betting and
+/// claiming only move numbers between the vault and the house.
+#[account]
+#[derive(Default)]
+pub struct PlayerVault {
+    pub player: Pubkey,
+    pub table: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+impl PlayerVault {
+    /// Moves `amount` of house funds (or a fresh deposit) into this vault.
+    pub fn credit(&mut self, table: &mut Table, amount: u64) -> Result<()> {
+        self.balance = self.balance.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        table.total_vault_balance = table
+            .total_vault_balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Moves `amount` out of this vault, to the house for a bet or out of the treasury on withdraw.
+    pub fn debit(&mut self, table: &mut Table, amount: u64) -> Result<()> {
+        require!(self.balance >= amount, ErrorCode::InsufficientVaultBalance);
+        self.balance -= amount;
+        table.total_vault_balance = table
+            .total_vault_balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        Ok(())
+    }
+}
+
+/// Takes a stake out of the player's vault when one is passed.
+/// Returns the part still to come from the player's wallet, zero when the vault covered it.
+pub fn take_stake(player_vault: Option<&mut PlayerVault>, table: &mut Table, amount: u64) -> Result<u64> {
+    match player_vault {
+        // Vault funds already sit in the treasury, so only the books move
+        Some(player_vault) => {
+            player_vault.debit(table, amount)?;
+            Ok(0)
+        }
+        None => Ok(amount),
+    }
+}
+
+/// Credits a payout to the player's vault when one is passed.
+/// Returns the part still to be paid to the player's wallet, zero when the vault took it.
+pub fn credit_payout(player_vault: Option<&mut PlayerVault>, table: &mut Table, amount: u64) -> Result<u64> {
+    match player_vault {
+        Some(player_vault) => {
+            player_vault.credit(table, amount)?;
+            Ok(0)
+        }
+        None => Ok(amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::harness::Game;
+    use crate::GameMode;
+
+    fn game(players: usize, house_funding: u64) -> Game {
+        Game::new(GameMode::FixedOdds, 250, players, house_funding)
+    }
+
+    #[test]
+    fn vaults_plus_house_equity_match_treasury() {
+        let mut game = game(3, 10_000_000);
+        game.assert_invariant();
+
+        game.deposit(0, 500_000);
+        game.deposit(1, 300_000);
+        game.deposit(2, 50_000);
+        game.assert_invariant();
+
+        game.bet_from_vault(0, 3, 100_000).unwrap();
+        game.bet_from_vault(1, 4, 100_000).unwrap();
+        game.bet_from_vault(2, 5, 50_000).unwrap();
+        game.assert_invariant();
+
+        // Player 0 wins at 6x less 2.5% commission, the others lose
+        game.settle_and_claim(3);
+        assert_eq!(game.vaults[0].balance, 400_000 + 585_000);
+        assert_eq!(game.table.outstanding_liabilities, 0);
+        game.assert_invariant();
+
+        game.withdraw(0, 985_000).unwrap();
+        game.withdraw(1, 200_000).unwrap();
+        game.assert_invariant();
+
+        assert_eq!(game.vaults[0].balance, 0);
+        assert_eq!(game.vaults[2].balance, 0);
+        assert_eq!(game.house_available(), 10_000_000 - 335_000);
+    }
+
+    #[test]
+    fn overdrawn_vault_is_rejected_and_leaves_books_intact() {
+        let mut game = game(2, 1_000_000);
+        game.deposit(0, 100_000);
+        game.deposit(1, 100_000);
+
+        let err = game.bet_from_vault(0, 3, 100_001).unwrap_err();
+        assert_eq!(err, ErrorCode::InsufficientVaultBalance.into());
+        assert_eq!(game.roll.open_bets, 0);
+        let err = game.withdraw(1, 200_000).unwrap_err();
+        assert_eq!(err, ErrorCode::InsufficientVaultBalance.into());
+
+        game.assert_invariant();
+    }
+
+    #[test]
+    fn winnings_go_back_where_the_stake_came_from() {
+        let mut game = game(2, 1_000_000);
+        game.deposit(0, 100_000);
+
+        game.bet_from_vault(0, 2, 100_000).unwrap();
+        game.bet(1, 2, 100_000).unwrap();
+        game.assert_invariant();
+
+        assert_eq!(game.settle_and_claim(2), vec![585_000, 585_000]);
+        assert_eq!(game.vaults[0].balance, 585_000);
+        assert_eq!(game.vaults[1].balance, 0);
+        assert_eq!(game.house_available(), 1_000_000 + 200_000 - 1_170_000);
+        game.assert_invariant();
+    }
+}